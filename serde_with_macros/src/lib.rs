@@ -37,8 +37,10 @@ use proc_macro::TokenStream;
 use proc_macro2::Span;
 use quote::quote;
 use syn::{
-    parse::Parser, Attribute, Error, Field, Fields, ItemEnum, ItemStruct, Meta, NestedMeta, Path,
-    Type,
+    parse::Parser,
+    parse_quote,
+    visit_mut::{self, VisitMut},
+    Attribute, Error, Field, Fields, ItemEnum, ItemStruct, Lit, Meta, NestedMeta, Path, Type,
 };
 
 /// Add `skip_serializing_if` annotations to [`Option`] fields.
@@ -140,15 +142,41 @@ pub fn skip_serializing_none(_args: TokenStream, input: TokenStream) -> TokenStr
 fn skip_serializing_none_do(input: TokenStream) -> Result<proc_macro2::TokenStream, String> {
     // For each field in the struct given by `input`, add the `skip_serializing_if` attribute,
     // if and only if, it is of type `Option`
+    apply_function_to_struct_and_enum_fields(input, skip_serializing_none_add_attr_to_field)
+}
+
+/// Apply function on every field of structs or enums
+///
+/// The function is called once for every field of a struct, or once for every field of every variant of an enum.
+/// Other items result in an error.
+fn apply_function_to_struct_and_enum_fields<F>(
+    input: TokenStream,
+    function: F,
+) -> Result<proc_macro2::TokenStream, String>
+where
+    F: Copy + Fn(&mut Field) -> Result<(), String>,
+{
+    /// Handle a single struct or a single enum variant
+    fn apply_on_fields<F>(fields: &mut Fields, function: F) -> Result<(), String>
+    where
+        F: Fn(&mut Field) -> Result<(), String>,
+    {
+        match fields {
+            // simple, no fields, do nothing
+            Fields::Unit => Ok(()),
+            Fields::Named(ref mut fields) => fields.named.iter_mut().try_for_each(function),
+            Fields::Unnamed(ref mut fields) => fields.unnamed.iter_mut().try_for_each(function),
+        }
+    }
+
     if let Ok(mut input) = syn::parse::<ItemStruct>(input.clone()) {
-        skip_serializing_none_handle_fields(&mut input.fields)?;
+        apply_on_fields(&mut input.fields, function)?;
         Ok(quote!(#input))
     } else if let Ok(mut input) = syn::parse::<ItemEnum>(input) {
         input
             .variants
             .iter_mut()
-            .map(|variant| skip_serializing_none_handle_fields(&mut variant.fields))
-            .collect::<Result<(), _>>()?;
+            .try_for_each(|variant| apply_on_fields(&mut variant.fields, function))?;
         Ok(quote!(#input))
     } else {
         Err("The attribute can only be applied to struct or enum definitions.".into())
@@ -211,73 +239,347 @@ fn field_has_attribute(field: &Field, namespace: &str, name: &str) -> bool {
 }
 
 /// Add the skip_serializing_if annotation to each field of the struct
-fn skip_serializing_none_add_attr_to_field<'a>(
-    fields: impl IntoIterator<Item = &'a mut Field>,
-) -> Result<(), String> {
-    fields.into_iter().map(|field| ->Result<(), String> {
-        if let Type::Path(path) = &field.ty.clone() {
-            if is_std_option(&path.path) {
-                let has_skip_serializing_if =
-                    field_has_attribute(&field, "serde", "skip_serializing_if");
+fn skip_serializing_none_add_attr_to_field(field: &mut Field) -> Result<(), String> {
+    if let Type::Path(path) = &field.ty.clone() {
+        if is_std_option(&path.path) {
+            let has_skip_serializing_if =
+                field_has_attribute(field, "serde", "skip_serializing_if");
 
-                // Remove the `serialize_always` attribute
-                let mut has_always_attr = false;
-                field.attrs.retain(|attr| {
-                    let has_attr = attr.path.is_ident("serialize_always");
-                    has_always_attr |= has_attr;
-                    !has_attr
-                });
+            // Remove the `serialize_always` attribute
+            let mut has_always_attr = false;
+            field.attrs.retain(|attr| {
+                let has_attr = attr.path.is_ident("serialize_always");
+                has_always_attr |= has_attr;
+                !has_attr
+            });
 
-                // Error on conflicting attributes
-                if has_always_attr && has_skip_serializing_if {
-                    let mut msg = r#"The attributes `serialize_always` and `serde(skip_serializing_if = "...")` cannot be used on the same field"#.to_string();
-                    if let Some(ident) = &field.ident {
-                       msg += ": `";
-                       msg += &ident.to_string();
-                       msg += "`";
-                    }
-                        msg +=".";
-                    return Err(msg);
+            // Error on conflicting attributes
+            if has_always_attr && has_skip_serializing_if {
+                let mut msg = r#"The attributes `serialize_always` and `serde(skip_serializing_if = "...")` cannot be used on the same field"#.to_string();
+                if let Some(ident) = &field.ident {
+                    msg += ": `";
+                    msg += &ident.to_string();
+                    msg += "`";
                 }
+                msg += ".";
+                return Err(msg);
+            }
 
-                // Do nothing if `skip_serializing_if` or `serialize_always` is already present
-                if has_skip_serializing_if || has_always_attr {
-                    return Ok(());
-                }
+            // Do nothing if `skip_serializing_if` or `serialize_always` is already present
+            if has_skip_serializing_if || has_always_attr {
+                return Ok(());
+            }
 
-                // Add the `skip_serializing_if` attribute
-                let attr_tokens = quote!(
-                    #[serde(skip_serializing_if = "Option::is_none")]
+            // Add the `skip_serializing_if` attribute
+            let attr_tokens = quote!(
+                #[serde(skip_serializing_if = "Option::is_none")]
+            );
+            let parser = Attribute::parse_outer;
+            let attrs = parser
+                .parse2(attr_tokens)
+                .expect("Static attr tokens should not panic");
+            field.attrs.extend(attrs);
+        } else {
+            // Warn on use of `serialize_always` on non-Option fields
+            let has_attr = field
+                .attrs
+                .iter()
+                .any(|attr| attr.path.is_ident("serialize_always"));
+            if has_attr {
+                return Err(
+                    "`serialize_always` may only be used on fields of type `Option`.".into(),
                 );
-                let parser = Attribute::parse_outer;
-                let attrs = parser
-                    .parse2(attr_tokens)
-                    .expect("Static attr tokens should not panic");
-                field.attrs.extend(attrs);
-            } else {
-                // Warn on use of `serialize_always` on non-Option fields
-                let has_attr= field.attrs.iter().any(|attr| {
-                    attr.path.is_ident("serialize_always")
-                });
-                if has_attr  {
-                    return Err("`serialize_always` may only be used on fields of type `Option`.".into());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Convenience macro to use the [`serde_as`] system.
+///
+/// The [`serde_as`] system is designed as a more flexible alternative to serde's with-annotation.
+/// The `#[serde_as]` attribute must be placed *before* the `#[derive]` attribute.
+/// Each field of a struct or enum can be annotated with `#[serde_as(...)]` to specify which transformations should be applied.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use serde_with::{serde_as, DisplayFromStr, Same};
+///
+/// #[serde_as]
+/// #[derive(Serialize, Deserialize)]
+/// struct Data {
+///     /// Serialize into number
+///     #[serde_as(as = "Same")]
+///     a: u32,
+///
+///     /// Serialize into String
+///     #[serde_as(as = "DisplayFromStr")]
+///     b: u32,
+///
+///     /// Serialize into a map from String to String
+///     #[serde_as(as = "Vec<(DisplayFromStr, DisplayFromStr)>")]
+///     c: Vec<(u32, String)>,
+///
+///     /// Use different conversions for serialization and deserialization
+///     #[serde_as(serialize_as = "DisplayFromStr", deserialize_as = "Same")]
+///     d: u32,
+/// }
+/// ```
+///
+/// # What this macro does
+///
+/// The `serde_as` macro only serves a convenience function.
+/// All the steps it performs, can easily be done manually, in case the cost of an attribute macro is deemed to high.
+/// The functionality can best be described with an example.
+///
+/// ```rust,ignore
+/// #[serde_as]
+/// #[derive(serde::Serialize)]
+/// struct Foo {
+///     #[serde_as(as = "Vec<_>")]
+///     bar: Vec<u32>,
+///
+///     #[serde_as(as = "Option<DisplayFromStr>")]
+///     baz: Option<u32>,
+/// }
+/// ```
+///
+/// 1. All the placeholder type `_` will be replaced with `::serde_with::Same`.
+///    The placeholder type `_` marks all the places where the types `Serialize` implementation should be used.
+///    In the example, it means that the `u32` values will serialize with the `Serialize` implementation of `u32`.
+///    The `Same` type implements `SerializeAs` whenever the underlying type implements `Serialize` and is used to make the two traits compatible.
+///
+/// 2. The `serde_as` attribute with an `as` key is converted into `#[serde(with = "::serde_with::As::<...>")]`.
+///    The `serialize_as` and `deserialize_as` keys are converted into
+///    `#[serde(serialize_with = "::serde_with::As::<...>::serialize")]` and
+///    `#[serde(deserialize_with = "::serde_with::As::<...>::deserialize")]` respectively.
+///
+/// 3. If the field and the `as`/`deserialize_as` type are both of type [`Option`], `#[serde(default)]` is added.
+///    Without it, a missing value would be an error, since `with` and `deserialize_with` disable serde's special handling of [`Option`] fields.
+///    An existing `default` annotation is kept unchanged.
+///
+/// After all these steps, the code snippet will have transformed into roughly this.
+///
+/// ```rust,ignore
+/// #[derive(serde::Serialize)]
+/// struct Foo {
+///     #[serde(with = "::serde_with::As::<Vec<::serde_with::Same>>")]
+///     bar: Vec<u32>,
+///
+///     #[serde(default)]
+///     #[serde(with = "::serde_with::As::<Option<DisplayFromStr>>")]
+///     baz: Option<u32>,
+/// }
+/// ```
+///
+/// # Limitations
+///
+/// The `as` key cannot be combined with the `serialize_as` or `deserialize_as` keys.
+/// A field using `serde_as` cannot also use serde's `with`, `serialize_with`, or `deserialize_with` annotations.
+/// Both cases result in a compile error.
+///
+/// The detection of [`Option`] fields has the same limitations as [`skip_serializing_none`].
+///
+/// [`serde_as`]: https://docs.rs/serde_with/*/serde_with/struct.As.html
+/// [`skip_serializing_none`]: macro@skip_serializing_none
+#[proc_macro_attribute]
+pub fn serde_as(_args: TokenStream, input: TokenStream) -> TokenStream {
+    let res = match apply_function_to_struct_and_enum_fields(input, serde_as_add_attr_to_field) {
+        Ok(res) => res,
+        Err(msg) => {
+            let span = Span::call_site();
+            Error::new(span, msg).to_compile_error()
+        }
+    };
+    TokenStream::from(res)
+}
+
+/// The keys supported by the `#[serde_as(...)]` field attribute
+#[derive(Default)]
+struct SerdeAsOptions {
+    r#as: Option<Type>,
+    serialize_as: Option<Type>,
+    deserialize_as: Option<Type>,
+}
+
+impl SerdeAsOptions {
+    /// Collect all `serde_as` attributes of the `field` and remove them afterwards
+    fn take_from_field(field: &mut Field) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut res = Ok(());
+        field.attrs.retain(|attr| {
+            if !attr.path.is_ident("serde_as") {
+                return true;
+            }
+            if res.is_ok() {
+                res = options.parse_attribute(attr);
+            }
+            false
+        });
+        res.map(|()| options)
+    }
+
+    fn parse_attribute(&mut self, attr: &Attribute) -> Result<(), String> {
+        const EXPECTED: &str = r#"Expected `serde_as(as = "...")`, `serde_as(serialize_as = "...")`, or `serde_as(deserialize_as = "...")`."#;
+
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list,
+            _ => return Err(EXPECTED.into()),
+        };
+        for nested in list.nested {
+            let name_value = match nested {
+                NestedMeta::Meta(Meta::NameValue(name_value)) => name_value,
+                _ => return Err(EXPECTED.into()),
+            };
+            let type_ = match &name_value.lit {
+                Lit::Str(lit) => lit
+                    .parse::<Type>()
+                    .map_err(|err| format!("Invalid type in `serde_as` attribute: {}", err))?,
+                _ => return Err(EXPECTED.into()),
+            };
+            let key = name_value
+                .path
+                .get_ident()
+                .map(ToString::to_string)
+                .unwrap_or_default();
+            let slot = match &*key {
+                "as" => &mut self.r#as,
+                "serialize_as" => &mut self.serialize_as,
+                "deserialize_as" => &mut self.deserialize_as,
+                _ => {
+                    return Err(format!(
+                        "Unknown key `{}` in `serde_as` attribute. {}",
+                        key, EXPECTED
+                    ))
                 }
+            };
+            if slot.is_some() {
+                return Err(format!("Duplicate key `{}` in `serde_as` attribute.", key));
             }
+            *slot = Some(type_);
         }
         Ok(())
-    }).collect()
+    }
 }
 
-/// Handle a single struct or a single enum variant
-fn skip_serializing_none_handle_fields(fields: &mut Fields) -> Result<(), String> {
-    match fields {
-        // simple, no fields, do nothing
-        Fields::Unit => Ok(()),
-        Fields::Named(ref mut fields) => {
-            skip_serializing_none_add_attr_to_field(fields.named.iter_mut())
+/// Add the `with`, `serialize_with`, or `deserialize_with` annotations for the `serde_as` attributes of the field
+fn serde_as_add_attr_to_field(field: &mut Field) -> Result<(), String> {
+    let options = SerdeAsOptions::take_from_field(field)?;
+    if options.r#as.is_none() && options.serialize_as.is_none() && options.deserialize_as.is_none()
+    {
+        return Ok(());
+    }
+
+    // Error on conflicting attributes
+    let field_name = field
+        .ident
+        .as_ref()
+        .map(|ident| format!(": `{}`", ident))
+        .unwrap_or_default();
+    if options.r#as.is_some()
+        && (options.serialize_as.is_some() || options.deserialize_as.is_some())
+    {
+        return Err(format!(
+            "The key `as` cannot be combined with `serialize_as` or `deserialize_as` in the `serde_as` attribute{}.",
+            field_name
+        ));
+    }
+    if ["with", "serialize_with", "deserialize_with"]
+        .iter()
+        .any(|name| field_has_attribute(field, "serde", name))
+    {
+        return Err(format!(
+            "The attribute `serde_as` cannot be combined with serde's `with`, `serialize_with`, or `deserialize_with`{}.",
+            field_name
+        ));
+    }
+
+    // `with` and `deserialize_with` disable the special handling of missing `Option` fields.
+    // Restore it by adding `default`, if the value is deserialized as `Option`.
+    if let Some(type_) = options.r#as.as_ref().or(options.deserialize_as.as_ref()) {
+        if is_std_option_type(&field.ty)
+            && is_std_option_type(type_)
+            && !field_has_path_attribute(field, "serde", "default")
+            && !field_has_attribute(field, "serde", "default")
+        {
+            field.attrs.push(parse_quote!(#[serde(default)]));
         }
-        Fields::Unnamed(ref mut fields) => {
-            skip_serializing_none_add_attr_to_field(fields.unnamed.iter_mut())
+    }
+
+    if let Some(type_) = options.r#as {
+        let type_ = replace_infer_type_with_same(type_);
+        let attr_value = quote!(::serde_with::As::<#type_>).to_string();
+        field.attrs.push(parse_quote!(#[serde(with = #attr_value)]));
+    }
+    if let Some(type_) = options.serialize_as {
+        let type_ = replace_infer_type_with_same(type_);
+        let attr_value = quote!(::serde_with::As::<#type_>::serialize).to_string();
+        field
+            .attrs
+            .push(parse_quote!(#[serde(serialize_with = #attr_value)]));
+    }
+    if let Some(type_) = options.deserialize_as {
+        let type_ = replace_infer_type_with_same(type_);
+        let attr_value = quote!(::serde_with::As::<#type_>::deserialize).to_string();
+        field
+            .attrs
+            .push(parse_quote!(#[serde(deserialize_with = #attr_value)]));
+    }
+    Ok(())
+}
+
+/// Return `true`, if the type is a path referring to `std::option::Option`
+fn is_std_option_type(type_: &Type) -> bool {
+    match type_ {
+        Type::Path(path) => path.qself.is_none() && is_std_option(&path.path),
+        Type::Group(group) => is_std_option_type(&group.elem),
+        Type::Paren(paren) => is_std_option_type(&paren.elem),
+        _ => false,
+    }
+}
+
+/// Determine if the `field` has a path attribute with given `namespace` and `name`
+///
+/// On the example of
+/// `#[serde(default)]`
+///
+/// * `serde` is the outermost path, here namespace
+/// * it contains a Meta::List
+/// * which contains in another Meta a Meta::Path
+/// * with the name being `default`
+fn field_has_path_attribute(field: &Field, namespace: &str, name: &str) -> bool {
+    field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident(namespace))
+        // Ignore non parsable attributes, as these are not important for us
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .any(|nested| match nested {
+            NestedMeta::Meta(Meta::Path(path)) => path.is_ident(name),
+            _ => false,
+        })
+}
+
+/// Replace all occurences of the placeholder type `_` with `::serde_with::Same`
+fn replace_infer_type_with_same(type_: Type) -> Type {
+    struct ReplaceInfer;
+
+    impl VisitMut for ReplaceInfer {
+        fn visit_type_mut(&mut self, type_: &mut Type) {
+            if let Type::Infer(_) = type_ {
+                *type_ = parse_quote!(::serde_with::Same);
+            } else {
+                visit_mut::visit_type_mut(self, type_);
+            }
         }
     }
+
+    let mut type_ = type_;
+    ReplaceInfer.visit_type_mut(&mut type_);
+    type_
 }
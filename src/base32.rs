@@ -0,0 +1,176 @@
+//! De/Serialization of base32 encoded bytes
+//!
+//! This modules is only available if using the `base32` feature of the crate.
+
+use crate::{
+    de::DeserializeAs,
+    ser::SerializeAs,
+    utils::bytes::{deserialize_decoded, serialize_encoded},
+};
+use data_encoding_crate::{DecodeKind, Encoding, Specification};
+use serde::{Deserializer, Serializer};
+use std::{convert::TryFrom, marker::PhantomData};
+
+pub use crate::{Padded, Padding, Unpadded};
+
+/// Serialize bytes as a base32 string
+///
+/// The type supports the same byte types as all [byte encodings](crate#byte-encodings), like `Vec<u8>` and `[u8; N]`.
+///
+/// The [`Alphabet`] selects the characters of the encoding, [`Rfc4648`] being the default.
+/// The [`Padding`] controls if `=` padding is written, [`Padded`] being the default and [`Unpadded`] the alternative.
+/// [`Crockford`]'s encoding does not define padding, so it is usually combined with [`Unpadded`].
+/// Deserialization accepts input with and without padding and ignores the case of the letters.
+/// Errors for invalid input report the byte offset of the offending character.
+///
+/// # Example
+///
+/// ```rust
+/// # use serde_derive::{Deserialize, Serialize};
+/// # use serde_with::{base32::{Base32, Crockford}, As, Unpadded};
+/// #
+/// #[derive(Deserialize, Serialize)]
+/// struct Secrets {
+///     #[serde(with = "As::<Base32>")]
+///     totp: Vec<u8>,
+///     #[serde(with = "As::<Base32<Crockford, Unpadded>>")]
+///     id: Vec<u8>,
+/// }
+///
+/// let secrets = Secrets {
+///     totp: b"foobar".to_vec(),
+///     id: b"foobar".to_vec(),
+/// };
+/// assert_eq!(
+///     r#"{"totp":"MZXW6YTBOI======","id":"CSQPYRK1E8"}"#,
+///     serde_json::to_string(&secrets).unwrap(),
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Base32<ALPHABET: Alphabet = Rfc4648, PADDING: Padding = Padded>(
+    PhantomData<(ALPHABET, PADDING)>,
+);
+
+/// Character set used by the [`Base32`] adapter
+pub trait Alphabet {
+    /// Specification of the encoding in the [`data-encoding`](https://docs.rs/data-encoding/) crate
+    ///
+    /// The padding is set by the [`Base32`] adapter and should be left empty.
+    fn specification() -> Specification;
+}
+
+/// Specification with the `symbols`, which also accepts their lowercase variants
+fn case_insensitive(symbols: &str) -> Specification {
+    let mut spec = Specification::new();
+    spec.symbols.push_str(symbols);
+    let uppercase: String = symbols.chars().filter(char::is_ascii_uppercase).collect();
+    spec.translate.from = uppercase.to_ascii_lowercase();
+    spec.translate.to = uppercase;
+    // Like the base64 decoder, ignore unused bits in the last character
+    spec.check_trailing_bits = false;
+    spec
+}
+
+/// Base32 alphabet of [RFC 4648](https://tools.ietf.org/html/rfc4648#section-6), using `A` to `Z` and `2` to `7`
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Rfc4648;
+
+impl Alphabet for Rfc4648 {
+    fn specification() -> Specification {
+        case_insensitive("ABCDEFGHIJKLMNOPQRSTUVWXYZ234567")
+    }
+}
+
+/// "Extended Hex" alphabet of [RFC 4648](https://tools.ietf.org/html/rfc4648#section-7), using `0` to `9` and `A` to `V`
+///
+/// The encoding preserves the sort order of the bytes and is used for DNS names, e.g., in NSEC3 records.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Rfc4648Hex;
+
+impl Alphabet for Rfc4648Hex {
+    fn specification() -> Specification {
+        case_insensitive("0123456789ABCDEFGHIJKLMNOPQRSTUV")
+    }
+}
+
+/// [Crockford's](https://www.crockford.com/base32.html) alphabet, which avoids the letters `I`, `L`, `O`, and `U`
+///
+/// Decoding reads `O` as `0` and `I` and `L` as `1` and skips hyphens.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Crockford;
+
+impl Alphabet for Crockford {
+    fn specification() -> Specification {
+        let mut spec = case_insensitive("0123456789ABCDEFGHJKMNPQRSTVWXYZ");
+        spec.translate.from.push_str("OoIiLl");
+        spec.translate.to.push_str("001111");
+        spec.ignore.push('-');
+        spec
+    }
+}
+
+fn encoding<ALPHABET>(padding: Option<char>) -> Encoding
+where
+    ALPHABET: Alphabet,
+{
+    let mut spec = ALPHABET::specification();
+    spec.padding = padding;
+    spec.encoding()
+        .expect("The base32 alphabets specify valid encodings")
+}
+
+fn encode<ALPHABET, PADDING>(bytes: &[u8]) -> String
+where
+    ALPHABET: Alphabet,
+    PADDING: Padding,
+{
+    let padding = if PADDING::PAD { Some('=') } else { None };
+    encoding::<ALPHABET>(padding).encode(bytes)
+}
+
+fn decode<ALPHABET>(value: &str) -> Result<Vec<u8>, String>
+where
+    ALPHABET: Alphabet,
+{
+    // Accept input with and without padding
+    let data = value.trim_end_matches('=');
+    encoding::<ALPHABET>(None)
+        .decode(data.as_bytes())
+        .map_err(|err| match err.kind {
+            DecodeKind::Symbol => format!(
+                "Invalid base32 string: invalid character {:?} at offset {}",
+                data[err.position..].chars().next().unwrap_or_default(),
+                err.position
+            ),
+            DecodeKind::Length => "Invalid base32 string: invalid length".into(),
+            kind => format!("Invalid base32 string: {} at offset {}", kind, err.position),
+        })
+}
+
+impl<T, ALPHABET, PADDING> SerializeAs<T> for Base32<ALPHABET, PADDING>
+where
+    T: AsRef<[u8]> + ?Sized,
+    ALPHABET: Alphabet,
+    PADDING: Padding,
+{
+    fn serialize_as<S>(source: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_encoded(source, encode::<ALPHABET, PADDING>, serializer)
+    }
+}
+
+impl<'de, T, ALPHABET, PADDING> DeserializeAs<'de, T> for Base32<ALPHABET, PADDING>
+where
+    T: TryFrom<Vec<u8>>,
+    ALPHABET: Alphabet,
+    PADDING: Padding,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_decoded(deserializer, decode::<ALPHABET>)
+    }
+}
@@ -0,0 +1,155 @@
+//! De/Serialization of base58 encoded bytes
+//!
+//! This modules is only available if using the `base58` feature of the crate.
+
+use crate::{
+    de::DeserializeAs,
+    ser::SerializeAs,
+    utils::bytes::{deserialize_decoded, serialize_encoded},
+};
+use bs58_crate::decode::Error;
+use serde::{Deserializer, Serializer};
+use std::convert::TryFrom;
+
+/// Serialize bytes as a base58 string
+///
+/// The type supports the same byte types as all [byte encodings](crate#byte-encodings), like `Vec<u8>` and `[u8; N]`.
+///
+/// The encoding uses the Bitcoin alphabet, which avoids the ambiguous characters `0`, `O`, `I`, and `l`.
+/// Leading zero bytes are encoded as `1`.
+/// [`Base58Check`] additionally appends a checksum.
+///
+/// # Example
+///
+/// ```rust
+/// # use serde_derive::{Deserialize, Serialize};
+/// # use serde_with::{base58::Base58, As};
+/// #
+/// #[derive(Deserialize, Serialize)]
+/// struct Message {
+///     #[serde(with = "As::<Base58>")]
+///     text: Vec<u8>,
+/// }
+///
+/// let message = Message {
+///     text: b"Hello World!".to_vec(),
+/// };
+/// assert_eq!(
+///     r#"{"text":"2NEpo7TZRRrLZSi2U"}"#,
+///     serde_json::to_string(&message).unwrap(),
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Base58;
+
+/// Serialize bytes as a base58 string with a checksum
+///
+/// This works like [`Base58`], but the encoded bytes are followed by the first four bytes of the double SHA-256 hash of the bytes.
+/// The checksum is computed by the [`bs58`](https://docs.rs/bs58/) crate.
+/// The checksum is verified and removed while deserializing.
+/// A version byte, like in Bitcoin addresses, needs to be part of the bytes.
+///
+/// # Example
+///
+/// ```rust
+/// # use serde_derive::{Deserialize, Serialize};
+/// # use serde_with::{base58::Base58Check, As};
+/// #
+/// #[derive(Deserialize, Serialize)]
+/// struct Wallet {
+///     #[serde(with = "As::<Base58Check>")]
+///     address: Vec<u8>,
+/// }
+///
+/// let wallet: Wallet =
+///     serde_json::from_str(r#"{"address":"16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM"}"#).unwrap();
+/// assert_eq!(21, wallet.address.len());
+/// assert!(serde_json::from_str::<Wallet>(r#"{"address":"16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvN"}"#).is_err());
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Base58Check;
+
+fn encode(bytes: &[u8]) -> String {
+    bs58_crate::encode(bytes).into_string()
+}
+
+fn decode(value: &str) -> Result<Vec<u8>, String> {
+    bs58_crate::decode(value)
+        .into_vec()
+        .map_err(|err| decode_error("base58", err))
+}
+
+fn encode_check(bytes: &[u8]) -> String {
+    bs58_crate::encode(bytes).with_check().into_string()
+}
+
+fn decode_check(value: &str) -> Result<Vec<u8>, String> {
+    bs58_crate::decode(value)
+        .with_check(None)
+        .into_vec()
+        .map_err(|err| decode_error("Base58Check", err))
+}
+
+fn decode_error(name: &str, err: Error) -> String {
+    match err {
+        Error::InvalidCharacter { character, index } => format!(
+            "Invalid {} string: invalid character {:?} at offset {}",
+            name, character, index
+        ),
+        Error::NonAsciiCharacter { index } => format!(
+            "Invalid {} string: non-ASCII character at offset {}",
+            name, index
+        ),
+        Error::InvalidChecksum { .. } => format!("Invalid {} string: checksum mismatch", name),
+        Error::NoChecksum => format!("Invalid {} string: too short to contain a checksum", name),
+        err => format!("Invalid {} string: {}", name, err),
+    }
+}
+
+impl<T> SerializeAs<T> for Base58
+where
+    T: AsRef<[u8]> + ?Sized,
+{
+    fn serialize_as<S>(source: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_encoded(source, encode, serializer)
+    }
+}
+
+impl<'de, T> DeserializeAs<'de, T> for Base58
+where
+    T: TryFrom<Vec<u8>>,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_decoded(deserializer, decode)
+    }
+}
+
+impl<T> SerializeAs<T> for Base58Check
+where
+    T: AsRef<[u8]> + ?Sized,
+{
+    fn serialize_as<S>(source: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_encoded(source, encode_check, serializer)
+    }
+}
+
+impl<'de, T> DeserializeAs<'de, T> for Base58Check
+where
+    T: TryFrom<Vec<u8>>,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_decoded(deserializer, decode_check)
+    }
+}
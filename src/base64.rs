@@ -0,0 +1,135 @@
+//! De/Serialization of base64 encoded bytes
+//!
+//! This modules is only available if using the `base64` feature of the crate.
+
+use crate::{
+    de::DeserializeAs,
+    ser::SerializeAs,
+    utils::bytes::{deserialize_decoded, serialize_encoded},
+};
+use base64_crate::{CharacterSet, Config, DecodeError};
+use serde::{Deserializer, Serializer};
+use std::{convert::TryFrom, marker::PhantomData};
+
+pub use crate::{Padded, Padding, Unpadded};
+
+/// Serialize bytes as a base64 string
+///
+/// The type supports the same byte types as all [byte encodings](crate#byte-encodings), like `Vec<u8>` and `[u8; N]`.
+///
+/// The [`Alphabet`] selects the characters of the encoding, [`Standard`] being the default.
+/// The [`Padding`] controls if `=` padding is written, [`Padded`] being the default and [`Unpadded`] the alternative.
+/// Deserialization accepts input with and without padding.
+/// Errors for invalid input report the byte offset of the offending character.
+///
+/// # Example
+///
+/// ```rust
+/// # use serde_derive::{Deserialize, Serialize};
+/// # use serde_with::{base64::{Base64, Unpadded, UrlSafe}, As};
+/// #
+/// #[derive(Deserialize, Serialize)]
+/// struct Blobs {
+///     #[serde(with = "As::<Base64>")]
+///     standard: Vec<u8>,
+///     #[serde(with = "As::<Base64<UrlSafe, Unpadded>>")]
+///     url_safe: Vec<u8>,
+/// }
+///
+/// let blobs = Blobs {
+///     standard: vec![0xfb, 0xff],
+///     url_safe: vec![0xfb, 0xff],
+/// };
+/// assert_eq!(
+///     r#"{"standard":"+/8=","url_safe":"-_8"}"#,
+///     serde_json::to_string(&blobs).unwrap(),
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Base64<ALPHABET: Alphabet = Standard, PADDING: Padding = Padded>(
+    PhantomData<(ALPHABET, PADDING)>,
+);
+
+/// Character set used by the [`Base64`] adapter
+pub trait Alphabet {
+    /// Character set of the [`base64`](https://docs.rs/base64/) crate
+    const CHARACTER_SET: CharacterSet;
+}
+
+/// Standard alphabet of [RFC 4648](https://tools.ietf.org/html/rfc4648#section-4), using `+` and `/`
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Standard;
+
+impl Alphabet for Standard {
+    const CHARACTER_SET: CharacterSet = CharacterSet::Standard;
+}
+
+/// URL and filename safe alphabet of [RFC 4648](https://tools.ietf.org/html/rfc4648#section-5), using `-` and `_`
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct UrlSafe;
+
+impl Alphabet for UrlSafe {
+    const CHARACTER_SET: CharacterSet = CharacterSet::UrlSafe;
+}
+
+/// Alphabet used by `crypt(3)`, starting with `./0123456789`
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Crypt;
+
+impl Alphabet for Crypt {
+    const CHARACTER_SET: CharacterSet = CharacterSet::Crypt;
+}
+
+fn config<ALPHABET, PADDING>() -> Config
+where
+    ALPHABET: Alphabet,
+    PADDING: Padding,
+{
+    Config::new(ALPHABET::CHARACTER_SET, PADDING::PAD)
+}
+
+impl<T, ALPHABET, PADDING> SerializeAs<T> for Base64<ALPHABET, PADDING>
+where
+    T: AsRef<[u8]> + ?Sized,
+    ALPHABET: Alphabet,
+    PADDING: Padding,
+{
+    fn serialize_as<S>(source: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_encoded(
+            source,
+            |bytes| base64_crate::encode_config(bytes, config::<ALPHABET, PADDING>()),
+            serializer,
+        )
+    }
+}
+
+impl<'de, T, ALPHABET, PADDING> DeserializeAs<'de, T> for Base64<ALPHABET, PADDING>
+where
+    T: TryFrom<Vec<u8>>,
+    ALPHABET: Alphabet,
+    PADDING: Padding,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_decoded(deserializer, |value| {
+            base64_crate::decode_config(value, config::<ALPHABET, PADDING>()).map_err(|err| {
+                match err {
+                    DecodeError::InvalidByte(offset, byte) => format!(
+                        "Invalid base64 string: invalid character {:?} at offset {}",
+                        byte as char, offset
+                    ),
+                    DecodeError::InvalidLastSymbol(offset, byte) => format!(
+                        "Invalid base64 string: invalid last character {:?} at offset {}",
+                        byte as char, offset
+                    ),
+                    DecodeError::InvalidLength => "Invalid base64 string: invalid length".into(),
+                }
+            })
+        })
+    }
+}
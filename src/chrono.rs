@@ -2,11 +2,44 @@
 //!
 //! This modules is only available if using the `chrono` feature of the crate.
 //!
+//! The `TimestampSeconds` family of adapters supports `DateTime<Utc>`, `DateTime<FixedOffset>`, `DateTime<Local>`, and `NaiveDateTime`.
+//! The `DurationSeconds` family of adapters, `Iso8601Duration`, and `DurationHumanString` support the signed `Duration`.
+//! [`FormattedDateTime`] de/serializes dates and times using custom [`DateTimeFormat`]s.
+//!
 //! [chrono]: https://docs.rs/chrono/
 
-use crate::{de::DeserializeAs, ser::SerializeAs};
-use chrono_crate::{DateTime, NaiveDateTime, Utc};
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::{
+    de::DeserializeAs,
+    ser::SerializeAs,
+    utils::{
+        duration::{
+            deserialize_iso8601_duration, format_human_duration, format_iso8601_duration,
+            DurationHumanStringVisitor, DurationSigned, Sign,
+        },
+        MICROS_PER_SEC, MILLIS_PER_SEC, NANOS_PER_SEC,
+    },
+    DurationHumanString, Flexible, Iso8601Designators, Iso8601Duration, Strict, Strictness,
+};
+use chrono_crate::{
+    format::SecondsFormat, DateTime, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime,
+    NaiveTime, TimeZone, Utc,
+};
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    fmt::{Display, Write},
+    marker::PhantomData,
+    time::UNIX_EPOCH,
+};
+
+/// Create a [`DateTime`] for the Unix Epoch using the [`Utc`] timezone
+fn unix_epoch_utc() -> DateTime<Utc> {
+    DateTime::<Utc>::from(UNIX_EPOCH)
+}
+
+/// Create a [`NaiveDateTime`] for the Unix Epoch
+fn unix_epoch_naive() -> NaiveDateTime {
+    unix_epoch_utc().naive_utc()
+}
 
 /// Deserialize a Unix timestamp with optional subsecond precision into a `DateTime<Utc>`.
 ///
@@ -175,3 +208,419 @@ impl<'de> DeserializeAs<'de, NaiveDateTime> for DateTime<Utc> {
         DateTime::<Utc>::deserialize(deserializer).map(|datetime| datetime.naive_utc())
     }
 }
+
+impl From<&Duration> for DurationSigned {
+    fn from(dur: &Duration) -> Self {
+        // A chrono Duration is limited to `i64::MAX` milliseconds in both directions,
+        // so the absolute value always fits into a std Duration.
+        match dur.to_std() {
+            Ok(dur) => DurationSigned::with_duration(Sign::Positive, dur),
+            Err(_) => DurationSigned::with_duration(
+                Sign::Negative,
+                (-*dur)
+                    .to_std()
+                    .expect("The negated value must be positive"),
+            ),
+        }
+    }
+}
+
+impl<TZ> From<&DateTime<TZ>> for DurationSigned
+where
+    TZ: TimeZone,
+{
+    fn from(datetime: &DateTime<TZ>) -> Self {
+        Self::from(&datetime.clone().signed_duration_since(unix_epoch_utc()))
+    }
+}
+
+impl From<&NaiveDateTime> for DurationSigned {
+    fn from(datetime: &NaiveDateTime) -> Self {
+        Self::from(&datetime.signed_duration_since(unix_epoch_naive()))
+    }
+}
+
+fn duration_into_chrono_duration<E>(dur: DurationSigned) -> Result<Duration, E>
+where
+    E: de::Error,
+{
+    let chrono_dur = Duration::from_std(dur.duration).map_err(|_| {
+        E::custom(format!(
+            "Invalid or out of range value '{}' for chrono::Duration",
+            dur
+        ))
+    })?;
+    match dur.sign {
+        Sign::Positive => Ok(chrono_dur),
+        Sign::Negative => Ok(-chrono_dur),
+    }
+}
+
+fn duration_into_datetime_utc<E>(dur: DurationSigned) -> Result<DateTime<Utc>, E>
+where
+    E: de::Error,
+{
+    let chrono_dur = Duration::from_std(dur.duration).ok();
+    let datetime = chrono_dur.and_then(|chrono_dur| match dur.sign {
+        Sign::Positive => unix_epoch_utc().checked_add_signed(chrono_dur),
+        Sign::Negative => unix_epoch_utc().checked_sub_signed(chrono_dur),
+    });
+    datetime.ok_or_else(|| {
+        E::custom(format!(
+            "Invalid or out of range value '{}' for DateTime",
+            dur
+        ))
+    })
+}
+
+fn duration_into_datetime_fixed_offset<E>(dur: DurationSigned) -> Result<DateTime<FixedOffset>, E>
+where
+    E: de::Error,
+{
+    duration_into_datetime_utc(dur).map(Into::into)
+}
+
+fn duration_into_datetime_local<E>(dur: DurationSigned) -> Result<DateTime<Local>, E>
+where
+    E: de::Error,
+{
+    duration_into_datetime_utc(dur).map(Into::into)
+}
+
+fn duration_into_naive_datetime<E>(dur: DurationSigned) -> Result<NaiveDateTime, E>
+where
+    E: de::Error,
+{
+    duration_into_datetime_utc(dur).map(|datetime| datetime.naive_utc())
+}
+
+macro_rules! use_timestamp_impls {
+    ($($target:ty, $to_target:path;)*) => {$(
+        use_duration_signed_ser!($target => TimestampSeconds, TimestampSecondsWithFrac, 1);
+        use_duration_signed_ser!($target => TimestampMilliSeconds, TimestampMilliSecondsWithFrac, MILLIS_PER_SEC);
+        use_duration_signed_ser!($target => TimestampMicroSeconds, TimestampMicroSecondsWithFrac, MICROS_PER_SEC);
+        use_duration_signed_ser!($target => TimestampNanoSeconds, TimestampNanoSecondsWithFrac, NANOS_PER_SEC);
+        use_duration_signed_de!($target, $to_target => TimestampSeconds, TimestampSecondsWithFrac, 1);
+        use_duration_signed_de!($target, $to_target => TimestampMilliSeconds, TimestampMilliSecondsWithFrac, MILLIS_PER_SEC);
+        use_duration_signed_de!($target, $to_target => TimestampMicroSeconds, TimestampMicroSecondsWithFrac, MICROS_PER_SEC);
+        use_duration_signed_de!($target, $to_target => TimestampNanoSeconds, TimestampNanoSecondsWithFrac, NANOS_PER_SEC);
+    )*};
+}
+use_timestamp_impls!(
+    DateTime<Utc>, duration_into_datetime_utc;
+    DateTime<FixedOffset>, duration_into_datetime_fixed_offset;
+    DateTime<Local>, duration_into_datetime_local;
+    NaiveDateTime, duration_into_naive_datetime;
+);
+
+use_duration_signed_ser!(Duration => DurationSeconds, DurationSecondsWithFrac, 1);
+use_duration_signed_ser!(Duration => DurationMilliSeconds, DurationMilliSecondsWithFrac, MILLIS_PER_SEC);
+use_duration_signed_ser!(Duration => DurationMicroSeconds, DurationMicroSecondsWithFrac, MICROS_PER_SEC);
+use_duration_signed_ser!(Duration => DurationNanoSeconds, DurationNanoSecondsWithFrac, NANOS_PER_SEC);
+use_duration_signed_de!(Duration, duration_into_chrono_duration => DurationSeconds, DurationSecondsWithFrac, 1);
+use_duration_signed_de!(Duration, duration_into_chrono_duration => DurationMilliSeconds, DurationMilliSecondsWithFrac, MILLIS_PER_SEC);
+use_duration_signed_de!(Duration, duration_into_chrono_duration => DurationMicroSeconds, DurationMicroSecondsWithFrac, MICROS_PER_SEC);
+use_duration_signed_de!(Duration, duration_into_chrono_duration => DurationNanoSeconds, DurationNanoSecondsWithFrac, NANOS_PER_SEC);
+
+impl<DESIGNATORS> SerializeAs<Duration> for Iso8601Duration<DESIGNATORS>
+where
+    DESIGNATORS: Iso8601Designators,
+{
+    fn serialize_as<S>(source: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        format_iso8601_duration(DurationSigned::from(source)).serialize(serializer)
+    }
+}
+
+impl<'de, DESIGNATORS> DeserializeAs<'de, Duration> for Iso8601Duration<DESIGNATORS>
+where
+    DESIGNATORS: Iso8601Designators,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<Duration, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        duration_into_chrono_duration(deserialize_iso8601_duration(
+            deserializer,
+            DESIGNATORS::DAYS_AND_WEEKS,
+        )?)
+    }
+}
+
+impl<STRICTNESS> SerializeAs<Duration> for DurationHumanString<STRICTNESS>
+where
+    STRICTNESS: Strictness,
+{
+    fn serialize_as<S>(source: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        format_human_duration(DurationSigned::from(source)).serialize(serializer)
+    }
+}
+
+impl<'de> DeserializeAs<'de, Duration> for DurationHumanString<Strict> {
+    fn deserialize_as<D>(deserializer: D) -> Result<Duration, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        duration_into_chrono_duration(
+            deserializer.deserialize_str(DurationHumanStringVisitor::new(false))?,
+        )
+    }
+}
+
+impl<'de> DeserializeAs<'de, Duration> for DurationHumanString<Flexible> {
+    fn deserialize_as<D>(deserializer: D) -> Result<Duration, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        duration_into_chrono_duration(
+            deserializer.deserialize_any(DurationHumanStringVisitor::new(true))?,
+        )
+    }
+}
+
+/// Format string for de/serializing dates and times with [`FormattedDateTime`]
+///
+/// The format uses the syntax of [`chrono::format::strftime`](https://docs.rs/chrono/*/chrono/format/strftime/index.html).
+pub trait DateTimeFormat {
+    /// Return the format string, like `%Y-%m-%d %H:%M:%S`
+    fn format() -> &'static str;
+
+    /// Use RFC 3339 with this precision for `DateTime`s instead of the format string
+    ///
+    /// Serialization writes `Z` for UTC and deserialization accepts both `Z` and numeric offsets.
+    /// The format string is still used for the naive types.
+    #[inline]
+    fn rfc3339() -> Option<SecondsFormat> {
+        None
+    }
+}
+
+/// Predefined format for RFC 3339 with whole seconds, like `2020-01-02T03:04:05Z` or `2020-01-02T04:04:05+01:00`
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Rfc3339Seconds;
+
+impl DateTimeFormat for Rfc3339Seconds {
+    #[inline]
+    fn format() -> &'static str {
+        "%Y-%m-%dT%H:%M:%S%:z"
+    }
+
+    #[inline]
+    fn rfc3339() -> Option<SecondsFormat> {
+        Some(SecondsFormat::Secs)
+    }
+}
+
+/// Predefined format for RFC 3339 with milliseconds, like `2020-01-02T03:04:05.678Z`
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Rfc3339MilliSeconds;
+
+impl DateTimeFormat for Rfc3339MilliSeconds {
+    #[inline]
+    fn format() -> &'static str {
+        "%Y-%m-%dT%H:%M:%S%.3f%:z"
+    }
+
+    #[inline]
+    fn rfc3339() -> Option<SecondsFormat> {
+        Some(SecondsFormat::Millis)
+    }
+}
+
+/// Predefined format for RFC 3339 with microseconds, like `2020-01-02T03:04:05.678901Z`
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Rfc3339MicroSeconds;
+
+impl DateTimeFormat for Rfc3339MicroSeconds {
+    #[inline]
+    fn format() -> &'static str {
+        "%Y-%m-%dT%H:%M:%S%.6f%:z"
+    }
+
+    #[inline]
+    fn rfc3339() -> Option<SecondsFormat> {
+        Some(SecondsFormat::Micros)
+    }
+}
+
+/// Predefined format for RFC 2822, like `Thu, 02 Jan 2020 03:04:05 +0000`
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Rfc2822;
+
+impl DateTimeFormat for Rfc2822 {
+    #[inline]
+    fn format() -> &'static str {
+        "%a, %d %b %Y %H:%M:%S %z"
+    }
+}
+
+/// De/Serialize dates and times as strings using the [`DateTimeFormat`] `F`
+///
+/// Supports `DateTime<Tz>`, `NaiveDateTime`, `NaiveDate`, and `NaiveTime`.
+/// Deserializing a `DateTime` requires the format to contain the offset.
+/// It is only available for the `Utc`, `Local`, and `FixedOffset` timezones.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono_crate::NaiveDateTime;
+/// # use serde_derive::{Deserialize, Serialize};
+/// use serde_with::chrono::{DateTimeFormat, FormattedDateTime};
+///
+/// struct PartnerFormat;
+///
+/// impl DateTimeFormat for PartnerFormat {
+///     fn format() -> &'static str {
+///         "%d/%m/%Y %H:%M"
+///     }
+/// }
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct A {
+///     #[serde(with = "serde_with::As::<FormattedDateTime<PartnerFormat>>")]
+///     date: NaiveDateTime,
+/// }
+///
+/// let a: A = serde_json::from_str(r#"{"date":"24/12/2019 18:30"}"#).unwrap();
+/// assert_eq!(a.date.to_string(), "2019-12-24 18:30:00");
+/// assert_eq!(r#"{"date":"24/12/2019 18:30"}"#, serde_json::to_string(&a).unwrap());
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct FormattedDateTime<F: DateTimeFormat>(PhantomData<F>);
+
+/// Format the value, while returning an error for invalid formats
+///
+/// The [`Display`] implementation of chrono fails if the format requires data which is not available,
+/// e.g., an offset for a `NaiveDateTime`.
+fn serialize_formatted<S, T>(value: T, format: &str, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Display,
+{
+    let mut output = String::new();
+    write!(output, "{}", value).map_err(|_| {
+        ser::Error::custom(format!(
+            "Failed to format the value with format '{}'",
+            format
+        ))
+    })?;
+    output.serialize(serializer)
+}
+
+fn deserialize_formatted<'de, D, T, E>(
+    deserializer: D,
+    format: &str,
+    parse: fn(&str, &str) -> Result<T, E>,
+) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    E: Display,
+{
+    let value = String::deserialize(deserializer)?;
+    parse(&value, format).map_err(|err| {
+        de::Error::custom(format!(
+            "Failed to parse '{}' with format '{}': {}",
+            value, format, err
+        ))
+    })
+}
+
+impl<F, TZ> SerializeAs<DateTime<TZ>> for FormattedDateTime<F>
+where
+    F: DateTimeFormat,
+    TZ: TimeZone,
+    TZ::Offset: Display,
+{
+    fn serialize_as<S>(source: &DateTime<TZ>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match F::rfc3339() {
+            Some(precision) => source
+                .to_rfc3339_opts(precision, true)
+                .serialize(serializer),
+            None => serialize_formatted(source.format(F::format()), F::format(), serializer),
+        }
+    }
+}
+
+macro_rules! formatted_naive_impl {
+    ($($ty:ty),*) => {$(
+        impl<F> SerializeAs<$ty> for FormattedDateTime<F>
+        where
+            F: DateTimeFormat,
+        {
+            fn serialize_as<S>(source: &$ty, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serialize_formatted(source.format(F::format()), F::format(), serializer)
+            }
+        }
+
+        impl<'de, F> DeserializeAs<'de, $ty> for FormattedDateTime<F>
+        where
+            F: DateTimeFormat,
+        {
+            fn deserialize_as<D>(deserializer: D) -> Result<$ty, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                deserialize_formatted(deserializer, F::format(), <$ty>::parse_from_str)
+            }
+        }
+    )*};
+}
+formatted_naive_impl!(NaiveDateTime, NaiveDate, NaiveTime);
+
+impl<'de, F> DeserializeAs<'de, DateTime<FixedOffset>> for FormattedDateTime<F>
+where
+    F: DateTimeFormat,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<DateTime<FixedOffset>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match F::rfc3339() {
+            Some(_) => deserialize_formatted(deserializer, "RFC 3339", |value, _| {
+                DateTime::<FixedOffset>::parse_from_rfc3339(value)
+            }),
+            None => deserialize_formatted(
+                deserializer,
+                F::format(),
+                DateTime::<FixedOffset>::parse_from_str,
+            ),
+        }
+    }
+}
+
+impl<'de, F> DeserializeAs<'de, DateTime<Utc>> for FormattedDateTime<F>
+where
+    F: DateTimeFormat,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        <Self as DeserializeAs<'de, DateTime<FixedOffset>>>::deserialize_as(deserializer)
+            .map(Into::into)
+    }
+}
+
+impl<'de, F> DeserializeAs<'de, DateTime<Local>> for FormattedDateTime<F>
+where
+    F: DateTimeFormat,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<DateTime<Local>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        <Self as DeserializeAs<'de, DateTime<FixedOffset>>>::deserialize_as(deserializer)
+            .map(Into::into)
+    }
+}
@@ -1,13 +1,29 @@
 use super::*;
-use crate::utils;
+use crate::{
+    duplicate_key_impls::{
+        DuplicateInsertsFirstWinsMap, DuplicateInsertsLastWinsSet, MapEntry,
+        PreventDuplicateInsertsMap, PreventDuplicateInsertsSet, SetValue,
+    },
+    key_value_map::KeyValue,
+    rust::bytes_or_string::BytesOrStringVisitor,
+    utils::{
+        self,
+        content::{Content, ContentRefDeserializer},
+        duration::{deserialize_iso8601_duration, DurationHumanStringVisitor, DurationSigned},
+    },
+};
 use serde::de::*;
 use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
     collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque},
-    convert::From,
+    convert::{From, TryInto},
     fmt::{self, Display},
     hash::{BuildHasher, Hash},
+    rc::Rc,
     str::FromStr,
-    time::Duration,
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, SystemTime},
 };
 
 impl<'de, T: Deserialize<'de>> DeserializeAs<'de, T> for SameAs<T> {
@@ -17,6 +33,13 @@ impl<'de, T: Deserialize<'de>> DeserializeAs<'de, T> for SameAs<T> {
     {
         T::deserialize(deserializer)
     }
+
+    fn deserialize_as_into<D>(deserializer: D, place: &mut T) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize_in_place(deserializer, place)
+    }
 }
 
 impl<'de, T, U> DeserializeAs<'de, Option<T>> for Option<U>
@@ -72,26 +95,623 @@ where
             }
         }
 
-        deserializer.deserialize_option(OptionVisitor::<T, U>(PhantomData))
+        deserializer.deserialize_option(OptionVisitor::<T, U>(PhantomData))
+    }
+
+    fn deserialize_as_into<D>(deserializer: D, place: &mut Option<T>) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct OptionInPlaceVisitor<'a, T, U> {
+            place: &'a mut Option<T>,
+            marker: PhantomData<U>,
+        }
+
+        impl<'a, 'de, T, U> Visitor<'de> for OptionInPlaceVisitor<'a, T, U>
+        where
+            U: DeserializeAs<'de, T>,
+        {
+            type Value = ();
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("option")
+            }
+
+            #[inline]
+            fn visit_unit<E>(self) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                *self.place = None;
+                Ok(())
+            }
+
+            #[inline]
+            fn visit_none<E>(self) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                *self.place = None;
+                Ok(())
+            }
+
+            #[inline]
+            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                match self.place {
+                    Some(value) => U::deserialize_as_into(deserializer, value),
+                    None => {
+                        *self.place = Some(U::deserialize_as(deserializer)?);
+                        Ok(())
+                    }
+                }
+            }
+        }
+
+        deserializer.deserialize_option(OptionInPlaceVisitor::<T, U> {
+            place,
+            marker: PhantomData,
+        })
+    }
+}
+
+impl<'de, T, U> DeserializeAs<'de, Vec<T>> for Vec<U>
+where
+    U: DeserializeAs<'de, T>,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<Vec<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct VecVisitor<T, U> {
+            marker: PhantomData<T>,
+            marker2: PhantomData<U>,
+        }
+
+        impl<'de, T, U> Visitor<'de> for VecVisitor<T, U>
+        where
+            U: DeserializeAs<'de, T>,
+        {
+            type Value = Vec<T>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a sequence")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut values = Vec::with_capacity(utils::size_hint_cautious(seq.size_hint()));
+
+                while let Some(value) = seq
+                    .next_element()?
+                    .map(|v: DeserializeAsWrap<T, U>| v.into_inner())
+                {
+                    values.push(value);
+                }
+
+                Ok(values)
+            }
+        }
+
+        let visitor = VecVisitor::<T, U> {
+            marker: PhantomData,
+            marker2: PhantomData,
+        };
+        deserializer.deserialize_seq(visitor)
+    }
+
+    fn deserialize_as_into<D>(deserializer: D, place: &mut Vec<T>) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct VecInPlaceVisitor<'a, T, U> {
+            place: &'a mut Vec<T>,
+            marker: PhantomData<U>,
+        }
+
+        impl<'a, 'de, T, U> Visitor<'de> for VecInPlaceVisitor<'a, T, U>
+        where
+            U: DeserializeAs<'de, T>,
+        {
+            type Value = ();
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a sequence")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let hint = utils::size_hint_cautious(seq.size_hint());
+                if let Some(additional) = hint.checked_sub(self.place.len()) {
+                    self.place.reserve(additional);
+                }
+
+                // Reuse the existing elements first
+                for i in 0..self.place.len() {
+                    let next = seq.next_element_seed(DeserializeAsIntoSeed::<T, U>::new(
+                        &mut self.place[i],
+                    ))?;
+                    if next.is_none() {
+                        self.place.truncate(i);
+                        return Ok(());
+                    }
+                }
+
+                while let Some(value) = seq
+                    .next_element()?
+                    .map(|v: DeserializeAsWrap<T, U>| v.into_inner())
+                {
+                    self.place.push(value);
+                }
+
+                Ok(())
+            }
+        }
+
+        let visitor = VecInPlaceVisitor::<T, U> {
+            place,
+            marker: PhantomData,
+        };
+        deserializer.deserialize_seq(visitor)
+    }
+}
+
+macro_rules! seq_impl {
+    (
+        $ty:ident < T $(: $tbound1:ident $(+ $tbound2:ident)*)* $(, $typaram:ident : $bound1:ident $(+ $bound2:ident)*)* >,
+        $access:ident,
+        $clear:expr,
+        $with_capacity:expr,
+        $reserve:expr,
+        $insert:expr
+    ) => {
+        impl<'de, T, U $(, $typaram)*> DeserializeAs<'de, $ty<T $(, $typaram)*>> for $ty<U $(, $typaram)*>
+        where
+            U: DeserializeAs<'de, T>,
+            $(T: $tbound1 $(+ $tbound2)*,)*
+            $($typaram: $bound1 $(+ $bound2)*),*
+        {
+            fn deserialize_as<D>(deserializer: D) -> Result<$ty<T $(, $typaram)*>, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct SeqVisitor<T, U $(, $typaram)*> {
+                    marker: PhantomData<$ty<T $(, $typaram)*>>,
+                    marker2: PhantomData<U>,
+                }
+
+                impl<'de, T, U $(, $typaram)*> Visitor<'de> for SeqVisitor<T, U $(, $typaram)*>
+                where
+                    U: DeserializeAs<'de, T>,
+                    $(T: $tbound1 $(+ $tbound2)*,)*
+                    $($typaram: $bound1 $(+ $bound2)*),*
+                {
+                    type Value = $ty<T $(, $typaram)*>;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        formatter.write_str("a sequence")
+                    }
+
+                    #[inline]
+                    fn visit_seq<A>(self, mut $access: A) -> Result<Self::Value, A::Error>
+                    where
+                        A: SeqAccess<'de>,
+                    {
+                        let mut values = $with_capacity;
+
+                        while let Some(value) = $access
+                            .next_element()?
+                            .map(|v: DeserializeAsWrap<T, U>| v.into_inner())
+                        {
+                            $insert(&mut values, value);
+                        }
+
+                        Ok(values)
+                    }
+                }
+
+                let visitor = SeqVisitor::<T, U $(, $typaram)*> {
+                    marker: PhantomData,
+                    marker2: PhantomData,
+                };
+                deserializer.deserialize_seq(visitor)
+            }
+
+            fn deserialize_as_into<D>(deserializer: D, place: &mut $ty<T $(, $typaram)*>) -> Result<(), D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct SeqInPlaceVisitor<'a, T, U $(, $typaram)*> {
+                    place: &'a mut $ty<T $(, $typaram)*>,
+                    marker: PhantomData<U>,
+                }
+
+                impl<'a, 'de, T, U $(, $typaram)*> Visitor<'de> for SeqInPlaceVisitor<'a, T, U $(, $typaram)*>
+                where
+                    U: DeserializeAs<'de, T>,
+                    $(T: $tbound1 $(+ $tbound2)*,)*
+                    $($typaram: $bound1 $(+ $bound2)*),*
+                {
+                    type Value = ();
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        formatter.write_str("a sequence")
+                    }
+
+                    #[inline]
+                    fn visit_seq<A>(self, mut $access: A) -> Result<Self::Value, A::Error>
+                    where
+                        A: SeqAccess<'de>,
+                    {
+                        $clear(&mut *self.place);
+                        $reserve(&mut *self.place, utils::size_hint_cautious($access.size_hint()));
+
+                        while let Some(value) = $access
+                            .next_element()?
+                            .map(|v: DeserializeAsWrap<T, U>| v.into_inner())
+                        {
+                            $insert(&mut *self.place, value);
+                        }
+
+                        Ok(())
+                    }
+                }
+
+                let visitor = SeqInPlaceVisitor::<T, U $(, $typaram)*> {
+                    place,
+                    marker: PhantomData,
+                };
+                deserializer.deserialize_seq(visitor)
+            }
+        }
+    };
+}
+
+/// Used by collections which do not support reserving capacity
+#[inline]
+fn nop_reserve<T>(_seq: T, _n: usize) {}
+
+seq_impl!(
+    BinaryHeap<T: Ord>,
+    seq,
+    BinaryHeap::clear,
+    BinaryHeap::with_capacity(utils::size_hint_cautious(seq.size_hint())),
+    BinaryHeap::reserve,
+    BinaryHeap::push
+);
+seq_impl!(
+    BTreeSet<T: Ord>,
+    seq,
+    BTreeSet::clear,
+    BTreeSet::new(),
+    nop_reserve,
+    BTreeSet::insert
+);
+seq_impl!(
+    HashSet<T: Eq + Hash, S: BuildHasher + Default>,
+    seq,
+    HashSet::clear,
+    HashSet::with_capacity_and_hasher(utils::size_hint_cautious(seq.size_hint()), S::default()),
+    HashSet::reserve,
+    HashSet::insert
+);
+seq_impl!(
+    LinkedList<T>,
+    seq,
+    LinkedList::clear,
+    LinkedList::new(),
+    nop_reserve,
+    LinkedList::push_back
+);
+seq_impl!(
+    VecDeque<T>,
+    seq,
+    VecDeque::clear,
+    VecDeque::with_capacity(utils::size_hint_cautious(seq.size_hint())),
+    VecDeque::reserve,
+    VecDeque::push_back
+);
+
+pub(crate) struct DeserializeAsWrap<T, U> {
+    value: T,
+    marker: PhantomData<U>,
+}
+
+impl<T, U> DeserializeAsWrap<T, U> {
+    pub(crate) fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<'de, T, U> Deserialize<'de> for DeserializeAsWrap<T, U>
+where
+    U: DeserializeAs<'de, T>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        U::deserialize_as(deserializer).map(|value| Self {
+            value,
+            marker: PhantomData,
+        })
+    }
+}
+
+/// Deserialize into an existing value using [`DeserializeAs::deserialize_as_into`]
+pub(crate) struct DeserializeAsIntoSeed<'a, T, U> {
+    place: &'a mut T,
+    marker: PhantomData<U>,
+}
+
+impl<'a, T, U> DeserializeAsIntoSeed<'a, T, U> {
+    pub(crate) fn new(place: &'a mut T) -> Self {
+        Self {
+            place,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'de, T, U> DeserializeSeed<'de> for DeserializeAsIntoSeed<'a, T, U>
+where
+    U: DeserializeAs<'de, T>,
+{
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        U::deserialize_as_into(deserializer, self.place)
+    }
+}
+
+macro_rules! pointer_impl {
+    ($($ty:ident $(,)?)+) => {$(
+        impl<'de, T, U> DeserializeAs<'de, $ty<T>> for $ty<U>
+        where
+            U: DeserializeAs<'de, T>,
+        {
+            fn deserialize_as<D>(deserializer: D) -> Result<$ty<T>, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                DeserializeAsWrap::<T, U>::deserialize(deserializer)
+                    .map(|value| $ty::new(value.into_inner()))
+            }
+        }
+    )+};
+}
+
+pointer_impl!(Rc, Arc, Cell, RefCell, Mutex, RwLock);
+
+impl<'de, T, U> DeserializeAs<'de, Box<T>> for Box<U>
+where
+    U: DeserializeAs<'de, T>,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<Box<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        DeserializeAsWrap::<T, U>::deserialize(deserializer)
+            .map(|value| Box::new(value.into_inner()))
+    }
+
+    fn deserialize_as_into<D>(deserializer: D, place: &mut Box<T>) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        U::deserialize_as_into(deserializer, &mut **place)
+    }
+}
+
+impl<'de, T, U> DeserializeAs<'de, Box<[T]>> for Box<[U]>
+where
+    U: DeserializeAs<'de, T>,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<Box<[T]>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        DeserializeAsWrap::<Vec<T>, Vec<U>>::deserialize(deserializer)
+            .map(|value| value.into_inner().into_boxed_slice())
+    }
+}
+
+impl<'de, U> DeserializeAs<'de, Box<str>> for Box<U>
+where
+    U: DeserializeAs<'de, String>,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<Box<str>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        DeserializeAsWrap::<String, U>::deserialize(deserializer)
+            .map(|value| value.into_inner().into_boxed_str())
+    }
+}
+
+impl<'de, 'a, T, U> DeserializeAs<'de, Cow<'a, T>> for Cow<'a, U>
+where
+    T: ToOwned + ?Sized,
+    U: ToOwned + ?Sized,
+    U::Owned: DeserializeAs<'de, T::Owned>,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<Cow<'a, T>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        DeserializeAsWrap::<T::Owned, U::Owned>::deserialize(deserializer)
+            .map(|value| Cow::Owned(value.into_inner()))
+    }
+}
+
+macro_rules! map_impl2 {
+    (
+        $ty:ident < K $(: $kbound1:ident $(+ $kbound2:ident)*)*, V $(, $typaram:ident : $bound1:ident $(+ $bound2:ident)*)* >,
+        $access:ident,
+        $with_capacity:expr,
+        $reserve:expr
+    ) => {
+        impl<'de, K, V, KU, VU $(, $typaram)*> DeserializeAs<'de, $ty<K, V $(, $typaram)*>> for $ty<KU, VU $(, $typaram)*>
+        where
+            KU: DeserializeAs<'de, K>,
+            VU: DeserializeAs<'de, V>,
+            $(K: $kbound1 $(+ $kbound2)*,)*
+            $($typaram: $bound1 $(+ $bound2)*),*
+        {
+            fn deserialize_as<D>(deserializer: D) -> Result<$ty<K, V $(, $typaram)*>, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct MapVisitor<K, V, KU, VU $(, $typaram)*> {
+                    marker: PhantomData<$ty<K, V $(, $typaram)*>>,
+                    marker2: PhantomData<$ty<KU, VU $(, $typaram)*>>,
+                }
+
+                impl<'de, K, V, KU, VU $(, $typaram)*> Visitor<'de> for MapVisitor<K, V, KU, VU $(, $typaram)*>
+                where
+                        KU: DeserializeAs<'de, K>,
+                        VU: DeserializeAs<'de, V>,
+                        $(K: $kbound1 $(+ $kbound2)*,)*
+                        $($typaram: $bound1 $(+ $bound2)*),*
+                {
+                    type Value = $ty<K, V $(, $typaram)*>;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        formatter.write_str("a map")
+                    }
+
+                    #[inline]
+                    fn visit_map<A>(self, mut $access: A) -> Result<Self::Value, A::Error>
+                    where
+                        A: MapAccess<'de>,
+                    {
+                        let mut values = $with_capacity;
+
+                        while let Some((key, value)) = ($access.next_entry())?.map(|(k, v): (DeserializeAsWrap::<K, KU>, DeserializeAsWrap::<V, VU>)| (k.into_inner(), v.into_inner())) {
+                            values.insert(key, value);
+                        }
+
+                        Ok(values)
+                    }
+                }
+
+                let visitor = MapVisitor::<K, V, KU, VU $(, $typaram)*> { marker: PhantomData, marker2: PhantomData };
+                deserializer.deserialize_map(visitor)
+            }
+
+            fn deserialize_as_into<D>(deserializer: D, place: &mut $ty<K, V $(, $typaram)*>) -> Result<(), D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct MapInPlaceVisitor<'a, K, V, KU, VU $(, $typaram)*> {
+                    place: &'a mut $ty<K, V $(, $typaram)*>,
+                    marker: PhantomData<$ty<KU, VU $(, $typaram)*>>,
+                }
+
+                impl<'a, 'de, K, V, KU, VU $(, $typaram)*> Visitor<'de> for MapInPlaceVisitor<'a, K, V, KU, VU $(, $typaram)*>
+                where
+                        KU: DeserializeAs<'de, K>,
+                        VU: DeserializeAs<'de, V>,
+                        $(K: $kbound1 $(+ $kbound2)*,)*
+                        $($typaram: $bound1 $(+ $bound2)*),*
+                {
+                    type Value = ();
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        formatter.write_str("a map")
+                    }
+
+                    #[inline]
+                    fn visit_map<A>(self, mut $access: A) -> Result<Self::Value, A::Error>
+                    where
+                        A: MapAccess<'de>,
+                    {
+                        self.place.clear();
+                        $reserve(&mut *self.place, utils::size_hint_cautious($access.size_hint()));
+
+                        while let Some((key, value)) = ($access.next_entry())?.map(|(k, v): (DeserializeAsWrap::<K, KU>, DeserializeAsWrap::<V, VU>)| (k.into_inner(), v.into_inner())) {
+                            self.place.insert(key, value);
+                        }
+
+                        Ok(())
+                    }
+                }
+
+                let visitor = MapInPlaceVisitor::<K, V, KU, VU $(, $typaram)*> { place, marker: PhantomData };
+                deserializer.deserialize_map(visitor)
+            }
+        }
+    }
+}
+
+map_impl2!(
+    BTreeMap<K: Ord, V>,
+    map,
+    BTreeMap::new(),
+    nop_reserve);
+
+map_impl2!(
+    HashMap<K: Eq + Hash, V, S: BuildHasher + Default>,
+    map,
+    HashMap::with_capacity_and_hasher(utils::size_hint_cautious(map.size_hint()), S::default()),
+    HashMap::reserve);
+
+impl<'de, K, V, VAs> DeserializeAs<'de, BTreeMap<K, V>> for MapKeysDisplayFromStr<VAs>
+where
+    K: FromStr + Ord,
+    K::Err: Display,
+    VAs: DeserializeAs<'de, V>,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<BTreeMap<K, V>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        BTreeMap::<DisplayFromStr, VAs>::deserialize_as(deserializer)
     }
 }
 
-impl<'de, T, U> DeserializeAs<'de, Vec<T>> for Vec<U>
+impl<'de, K, V, VAs, S> DeserializeAs<'de, HashMap<K, V, S>> for MapKeysDisplayFromStr<VAs>
 where
-    U: DeserializeAs<'de, T>,
+    K: FromStr + Eq + Hash,
+    K::Err: Display,
+    VAs: DeserializeAs<'de, V>,
+    S: BuildHasher + Default,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<HashMap<K, V, S>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        HashMap::<DisplayFromStr, VAs, S>::deserialize_as(deserializer)
+    }
+}
+
+impl<'de, T, TAs, HOOK> DeserializeAs<'de, Vec<T>> for VecSkipError<TAs, HOOK>
+where
+    TAs: DeserializeAs<'de, T>,
+    HOOK: SkipErrorHook,
 {
     fn deserialize_as<D>(deserializer: D) -> Result<Vec<T>, D::Error>
     where
         D: Deserializer<'de>,
     {
-        struct VecVisitor<T, U> {
-            marker: PhantomData<T>,
-            marker2: PhantomData<U>,
+        struct SeqVisitor<T, TAs, HOOK> {
+            is_human_readable: bool,
+            marker: PhantomData<(T, TAs, HOOK)>,
         }
 
-        impl<'de, T, U> Visitor<'de> for VecVisitor<T, U>
+        impl<'de, T, TAs, HOOK> Visitor<'de> for SeqVisitor<T, TAs, HOOK>
         where
-            U: DeserializeAs<'de, T>,
+            TAs: DeserializeAs<'de, T>,
+            HOOK: SkipErrorHook,
         {
             type Value = Vec<T>;
 
@@ -104,62 +724,40 @@ where
                 A: SeqAccess<'de>,
             {
                 let mut values = Vec::with_capacity(utils::size_hint_cautious(seq.size_hint()));
-
-                while let Some(value) = seq
-                    .next_element()?
-                    .map(|v: DeserializeAsWrap<T, U>| v.into_inner())
-                {
-                    values.push(value);
+                let mut index = 0;
+                while let Some(content) = seq.next_element::<Content<'de>>()? {
+                    match TAs::deserialize_as(ContentRefDeserializer::<A::Error>::new(
+                        &content,
+                        self.is_human_readable,
+                    )) {
+                        Ok(value) => values.push(value),
+                        Err(err) => HOOK::skipped(index, &err),
+                    }
+                    index += 1;
                 }
-
                 Ok(values)
             }
         }
 
-        let visitor = VecVisitor::<T, U> {
+        let visitor = SeqVisitor::<T, TAs, HOOK> {
+            is_human_readable: deserializer.is_human_readable(),
             marker: PhantomData,
-            marker2: PhantomData,
         };
         deserializer.deserialize_seq(visitor)
     }
 }
 
-pub(crate) struct DeserializeAsWrap<T, U> {
-    value: T,
-    marker: PhantomData<U>,
-}
-
-impl<T, U> DeserializeAsWrap<T, U> {
-    pub(crate) fn into_inner(self) -> T {
-        self.value
-    }
-}
-
-impl<'de, T, U> Deserialize<'de> for DeserializeAsWrap<T, U>
-where
-    U: DeserializeAs<'de, T>,
-{
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        U::deserialize_as(deserializer).map(|value| Self {
-            value,
-            marker: PhantomData,
-        })
-    }
-}
-
-macro_rules! map_impl2 {
+macro_rules! map_skip_error_impl {
     (
         $ty:ident < K $(: $kbound1:ident $(+ $kbound2:ident)*)*, V $(, $typaram:ident : $bound1:ident $(+ $bound2:ident)*)* >,
         $access:ident,
         $with_capacity:expr
     ) => {
-        impl<'de, K, V, KU, VU $(, $typaram)*> DeserializeAs<'de, $ty<K, V $(, $typaram)*>> for $ty<KU, VU $(, $typaram)*>
+        impl<'de, K, V, KAs, VAs, HOOK $(, $typaram)*> DeserializeAs<'de, $ty<K, V $(, $typaram)*>> for MapSkipError<KAs, VAs, HOOK>
         where
-            KU: DeserializeAs<'de, K>,
-            VU: DeserializeAs<'de, V>,
+            KAs: DeserializeAs<'de, K>,
+            VAs: DeserializeAs<'de, V>,
+            HOOK: SkipErrorHook,
             $(K: $kbound1 $(+ $kbound2)*,)*
             $($typaram: $bound1 $(+ $bound2)*),*
         {
@@ -167,17 +765,18 @@ macro_rules! map_impl2 {
             where
                 D: Deserializer<'de>,
             {
-                struct MapVisitor<K, V, KU, VU $(, $typaram)*> {
-                    marker: PhantomData<$ty<K, V $(, $typaram)*>>,
-                    marker2: PhantomData<$ty<KU, VU $(, $typaram)*>>,
+                struct MapVisitor<K, V, KAs, VAs, HOOK $(, $typaram)*> {
+                    is_human_readable: bool,
+                    marker: PhantomData<($ty<K, V $(, $typaram)*>, KAs, VAs, HOOK)>,
                 }
 
-                impl<'de, K, V, KU, VU $(, $typaram)*> Visitor<'de> for MapVisitor<K, V, KU, VU $(, $typaram)*>
+                impl<'de, K, V, KAs, VAs, HOOK $(, $typaram)*> Visitor<'de> for MapVisitor<K, V, KAs, VAs, HOOK $(, $typaram)*>
                 where
-                        KU: DeserializeAs<'de, K>,
-                        VU: DeserializeAs<'de, V>,
-                        $(K: $kbound1 $(+ $kbound2)*,)*
-                        $($typaram: $bound1 $(+ $bound2)*),*
+                    KAs: DeserializeAs<'de, K>,
+                    VAs: DeserializeAs<'de, V>,
+                    HOOK: SkipErrorHook,
+                    $(K: $kbound1 $(+ $kbound2)*,)*
+                    $($typaram: $bound1 $(+ $bound2)*),*
                 {
                     type Value = $ty<K, V $(, $typaram)*>;
 
@@ -185,38 +784,209 @@ macro_rules! map_impl2 {
                         formatter.write_str("a map")
                     }
 
-                    #[inline]
                     fn visit_map<A>(self, mut $access: A) -> Result<Self::Value, A::Error>
                     where
                         A: MapAccess<'de>,
                     {
                         let mut values = $with_capacity;
 
-                        while let Some((key, value)) = ($access.next_entry())?.map(|(k, v): (DeserializeAsWrap::<K, KU>, DeserializeAsWrap::<V, VU>)| (k.into_inner(), v.into_inner())) {
-                            values.insert(key, value);
+                        let mut index = 0;
+                        while let Some((key, value)) = $access.next_entry::<Content<'de>, Content<'de>>()? {
+                            let key = KAs::deserialize_as(ContentRefDeserializer::<A::Error>::new(&key, self.is_human_readable));
+                            let value = VAs::deserialize_as(ContentRefDeserializer::<A::Error>::new(&value, self.is_human_readable));
+                            match (key, value) {
+                                (Ok(key), Ok(value)) => {
+                                    values.insert(key, value);
+                                }
+                                (Err(err), _) | (_, Err(err)) => HOOK::skipped(index, &err),
+                            }
+                            index += 1;
                         }
 
                         Ok(values)
                     }
                 }
 
-                let visitor = MapVisitor::<K, V, KU, VU $(, $typaram)*> { marker: PhantomData, marker2: PhantomData };
+                let visitor = MapVisitor::<K, V, KAs, VAs, HOOK $(, $typaram)*> {
+                    is_human_readable: deserializer.is_human_readable(),
+                    marker: PhantomData,
+                };
                 deserializer.deserialize_map(visitor)
             }
         }
     }
 }
 
-map_impl2!(
+map_skip_error_impl!(
     BTreeMap<K: Ord, V>,
     map,
     BTreeMap::new());
 
-map_impl2!(
+map_skip_error_impl!(
     HashMap<K: Eq + Hash, V, S: BuildHasher + Default>,
     map,
     HashMap::with_capacity_and_hasher(utils::size_hint_cautious(map.size_hint()), S::default()));
 
+/// Visitor for sets, which inserts the elements according to a duplicate policy
+///
+/// The `insert` function returns `false` if the value must be rejected as a duplicate.
+struct DuplicatePolicySetVisitor<SET, T, TAs> {
+    new: fn(Option<usize>) -> SET,
+    insert: fn(&mut SET, T) -> bool,
+    marker: PhantomData<TAs>,
+}
+
+impl<'de, SET, T, TAs> Visitor<'de> for DuplicatePolicySetVisitor<SET, T, TAs>
+where
+    TAs: DeserializeAs<'de, T>,
+{
+    type Value = SET;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    #[inline]
+    fn visit_seq<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = (self.new)(access.size_hint());
+
+        while let Some(value) = access
+            .next_element()?
+            .map(|v: DeserializeAsWrap<T, TAs>| v.into_inner())
+        {
+            if !(self.insert)(&mut values, value) {
+                return Err(Error::custom("invalid entry: found duplicate value"));
+            }
+        }
+
+        Ok(values)
+    }
+}
+
+/// Visitor for maps, which inserts the entries according to a duplicate policy
+///
+/// The `insert` function returns `false` if the entry must be rejected as a duplicate.
+struct DuplicatePolicyMapVisitor<MAP, K, KAs, V, VAs> {
+    new: fn(Option<usize>) -> MAP,
+    insert: fn(&mut MAP, K, V) -> bool,
+    marker: PhantomData<(KAs, VAs)>,
+}
+
+impl<'de, MAP, K, KAs, V, VAs> Visitor<'de> for DuplicatePolicyMapVisitor<MAP, K, KAs, V, VAs>
+where
+    KAs: DeserializeAs<'de, K>,
+    VAs: DeserializeAs<'de, V>,
+{
+    type Value = MAP;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a map")
+    }
+
+    #[inline]
+    fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut values = (self.new)(access.size_hint());
+
+        while let Some((key, value)) = access.next_entry()?.map(
+            |(k, v): (DeserializeAsWrap<K, KAs>, DeserializeAsWrap<V, VAs>)| {
+                (k.into_inner(), v.into_inner())
+            },
+        ) {
+            if !(self.insert)(&mut values, key, value) {
+                return Err(Error::custom("invalid entry: found duplicate key"));
+            }
+        }
+
+        Ok(values)
+    }
+}
+
+impl<'de, SET, TAs> DeserializeAs<'de, SET> for SetPreventDuplicates<TAs>
+where
+    SET: SetValue + PreventDuplicateInsertsSet<<SET as SetValue>::Value>,
+    TAs: DeserializeAs<'de, SET::Value>,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<SET, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(DuplicatePolicySetVisitor::<_, SET::Value, TAs> {
+            new: PreventDuplicateInsertsSet::new,
+            insert: PreventDuplicateInsertsSet::insert,
+            marker: PhantomData,
+        })
+    }
+}
+
+impl<'de, SET, TAs> DeserializeAs<'de, SET> for SetLastValueWins<TAs>
+where
+    SET: SetValue + DuplicateInsertsLastWinsSet<<SET as SetValue>::Value>,
+    TAs: DeserializeAs<'de, SET::Value>,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<SET, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(DuplicatePolicySetVisitor::<_, SET::Value, TAs> {
+            new: DuplicateInsertsLastWinsSet::new,
+            insert: |set: &mut SET, value| {
+                DuplicateInsertsLastWinsSet::replace(set, value);
+                true
+            },
+            marker: PhantomData,
+        })
+    }
+}
+
+impl<'de, MAP, KAs, VAs> DeserializeAs<'de, MAP> for MapPreventDuplicates<KAs, VAs>
+where
+    MAP: MapEntry + PreventDuplicateInsertsMap<<MAP as MapEntry>::Key, <MAP as MapEntry>::Value>,
+    KAs: DeserializeAs<'de, MAP::Key>,
+    VAs: DeserializeAs<'de, MAP::Value>,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<MAP, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(
+            DuplicatePolicyMapVisitor::<_, MAP::Key, KAs, MAP::Value, VAs> {
+                new: PreventDuplicateInsertsMap::new,
+                insert: PreventDuplicateInsertsMap::insert,
+                marker: PhantomData,
+            },
+        )
+    }
+}
+
+impl<'de, MAP, KAs, VAs> DeserializeAs<'de, MAP> for MapFirstKeyWins<KAs, VAs>
+where
+    MAP: MapEntry + DuplicateInsertsFirstWinsMap<<MAP as MapEntry>::Key, <MAP as MapEntry>::Value>,
+    KAs: DeserializeAs<'de, MAP::Key>,
+    VAs: DeserializeAs<'de, MAP::Value>,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<MAP, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(
+            DuplicatePolicyMapVisitor::<_, MAP::Key, KAs, MAP::Value, VAs> {
+                new: DuplicateInsertsFirstWinsMap::new,
+                insert: |map: &mut MAP, key, value| {
+                    DuplicateInsertsFirstWinsMap::insert(map, key, value);
+                    true
+                },
+                marker: PhantomData,
+            },
+        )
+    }
+}
+
 impl<'de, T> DeserializeAs<'de, T> for DisplayFromStr
 where
     T: FromStr,
@@ -408,6 +1178,13 @@ impl<'de, T: Deserialize<'de>> DeserializeAs<'de, T> for Same {
     {
         T::deserialize(deserializer)
     }
+
+    fn deserialize_as_into<D>(deserializer: D, place: &mut T) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize_in_place(deserializer, place)
+    }
 }
 
 macro_rules! map_as_tuple_seq {
@@ -496,10 +1273,36 @@ where
             }
         }
 
+        // Unlike `NoneAs`, this does not buffer the input, so non-self-describing formats work too
         deserializer.deserialize_str(OptionStringEmptyNone(PhantomData))
     }
 }
 
+impl<'de, SENTINEL, T, TAs> DeserializeAs<'de, Option<T>> for NoneAs<SENTINEL, TAs>
+where
+    SENTINEL: Sentinel,
+    TAs: DeserializeAs<'de, T>,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let is_human_readable = deserializer.is_human_readable();
+        let content = Content::deserialize(deserializer)?;
+        if SENTINEL::sentinels()
+            .iter()
+            .any(|sentinel| content.is_sentinel(sentinel))
+        {
+            return Ok(None);
+        }
+        TAs::deserialize_as(ContentRefDeserializer::<D::Error>::new(
+            &content,
+            is_human_readable,
+        ))
+        .map(Some)
+    }
+}
+
 macro_rules! tuple_seq_as_map_impl_intern {
     ($tyorig:ident < (K $(: $($kbound:ident $(+)?)+)?, V $(: $($vbound:ident $(+)?)+)?)>, $ty:ident <KAs, VAs>) => {
         #[allow(clippy::implicit_hasher)]
@@ -649,151 +1452,247 @@ impl<'de> DeserializeAs<'de, Vec<u8>> for BytesOrString {
     }
 }
 
-struct DurationVisitiorFlexible;
-impl<'de> Visitor<'de> for DurationVisitiorFlexible {
-    type Value = Duration;
-
-    fn expecting(&self, formatter: &mut fmt::Formatter) -> ::std::fmt::Result {
-        formatter.write_str("an integer, a float, or a string containing a number")
-    }
-
-    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+impl<'de> DeserializeAs<'de, Vec<u8>> for Bytes {
+    fn deserialize_as<D>(deserializer: D) -> Result<Vec<u8>, D::Error>
     where
-        E: Error,
+        D: Deserializer<'de>,
     {
-        if value >= 0 {
-            Ok(Duration::new(value as u64, 0))
-        } else {
-            Err(Error::custom(format!(
-                "Negative values are not supported for Duration. Found {}",
-                value
-            )))
-        }
+        deserializer
+            .deserialize_byte_buf(BytesOrStringVisitor)
+            .map(Cow::into_owned)
     }
+}
 
-    fn visit_u64<E>(self, secs: u64) -> Result<Self::Value, E>
+impl<'de> DeserializeAs<'de, Box<[u8]>> for Bytes {
+    fn deserialize_as<D>(deserializer: D) -> Result<Box<[u8]>, D::Error>
     where
-        E: Error,
+        D: Deserializer<'de>,
     {
-        Ok(Duration::new(secs, 0))
+        <Bytes as DeserializeAs<'de, Vec<u8>>>::deserialize_as(deserializer)
+            .map(Vec::into_boxed_slice)
     }
+}
 
-    fn visit_f64<E>(self, secs: f64) -> Result<Self::Value, E>
+impl<'de> DeserializeAs<'de, &'de [u8]> for Bytes {
+    fn deserialize_as<D>(deserializer: D) -> Result<&'de [u8], D::Error>
     where
-        E: Error,
+        D: Deserializer<'de>,
     {
-        utils::duration_from_secs_f64(secs).map_err(Error::custom)
+        <&'de [u8]>::deserialize(deserializer)
     }
+}
 
-    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+impl<'de> DeserializeAs<'de, Cow<'de, [u8]>> for Bytes {
+    fn deserialize_as<D>(deserializer: D) -> Result<Cow<'de, [u8]>, D::Error>
     where
-        E: Error,
+        D: Deserializer<'de>,
     {
-        let parts: Vec<_> = value.split('.').collect();
-
-        match *parts.as_slice() {
-            [seconds] => {
-                if let Ok(seconds) = u64::from_str_radix(seconds, 10) {
-                    Ok(Duration::new(seconds, 0))
-                } else {
-                    Err(Error::invalid_value(Unexpected::Str(value), &self))
-                }
-            }
-            [seconds, subseconds] => {
-                if let Ok(seconds) = u64::from_str_radix(seconds, 10) {
-                    let subseclen = subseconds.chars().count() as u32;
-                    if subseclen > 9 {
-                        return Err(Error::custom(format!(
-                                    "Duration only support nanosecond precision but '{}' has more than 9 digits.",
-                                    value
-                                )));
-                    }
+        deserializer.deserialize_bytes(BytesOrStringVisitor)
+    }
+}
 
-                    if let Ok(mut subseconds) = u32::from_str_radix(subseconds, 10) {
-                        // convert subseconds to nanoseconds (10^-9), require 9 places for nanoseconds
-                        subseconds *= 10u32.pow(9 - subseclen);
-                        Ok(Duration::new(seconds, subseconds))
-                    } else {
-                        Err(Error::invalid_value(Unexpected::Str(value), &self))
-                    }
-                } else {
-                    Err(Error::invalid_value(Unexpected::Str(value), &self))
+macro_rules! bytes_array_impl {
+    ($($len:tt)*) => {$(
+        impl<'de> DeserializeAs<'de, [u8; $len]> for Bytes {
+            fn deserialize_as<D>(deserializer: D) -> Result<[u8; $len], D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let bytes = deserializer.deserialize_bytes(BytesOrStringVisitor)?;
+                if bytes.len() != $len {
+                    return Err(Error::invalid_length(
+                        bytes.len(),
+                        &concat!("an array of ", stringify!($len), " bytes"),
+                    ));
                 }
+                let mut array = [0; $len];
+                array.copy_from_slice(&bytes);
+                Ok(array)
             }
-
-            _ => Err(Error::invalid_value(Unexpected::Str(value), &self)),
         }
-    }
+    )*};
 }
+bytes_array_impl!(0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32);
 
-impl<'de> DeserializeAs<'de, Duration> for DurationSeconds<Integer, Strict> {
-    fn deserialize_as<D>(deserializer: D) -> Result<Duration, D::Error>
+impl<'de, T, U> DeserializeAs<'de, T> for FromInto<U>
+where
+    U: Into<T>,
+    U: Deserialize<'de>,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<T, D::Error>
     where
         D: Deserializer<'de>,
     {
-        u64::deserialize(deserializer).map(|secs| Duration::new(secs, 0))
+        Ok(U::deserialize(deserializer)?.into())
     }
 }
 
-impl<'de> DeserializeAs<'de, Duration> for DurationSeconds<f64, Strict> {
-    fn deserialize_as<D>(deserializer: D) -> Result<Duration, D::Error>
+impl<'de, T, U> DeserializeAs<'de, T> for TryFromInto<U>
+where
+    U: TryInto<T>,
+    <U as TryInto<T>>::Error: Display,
+    U: Deserialize<'de>,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<T, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let val = f64::deserialize(deserializer)?;
-        utils::duration_from_secs_f64(val).map_err(Error::custom)
+        U::deserialize(deserializer)?
+            .try_into()
+            .map_err(Error::custom)
     }
 }
 
-impl<'de> DeserializeAs<'de, Duration> for DurationSeconds<String, Strict> {
-    fn deserialize_as<D>(deserializer: D) -> Result<Duration, D::Error>
+macro_rules! pick_first_impl {
+    ($len:literal $($n:tt $tas:ident)+) => {
+        impl<'de, T, $($tas,)+> DeserializeAs<'de, T> for PickFirst<($($tas,)+)>
+        where
+            $($tas: DeserializeAs<'de, T>,)+
+        {
+            fn deserialize_as<D>(deserializer: D) -> Result<T, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let is_human_readable = deserializer.is_human_readable();
+                let content = Content::deserialize(deserializer)?;
+                let mut errors: Vec<String> = Vec::with_capacity($len);
+                $(
+                    match $tas::deserialize_as(
+                        ContentRefDeserializer::<D::Error>::new(&content, is_human_readable),
+                    ) {
+                        Ok(value) => return Ok(value),
+                        Err(err) => errors.push(format!("alternative {} failed with: {}", $n + 1, err)),
+                    }
+                )+
+                Err(Error::custom(format_args!(
+                    "PickFirst could not deserialize data: {}",
+                    errors.join("; ")
+                )))
+            }
+        }
+    };
+}
+
+pick_first_impl!(2 0 As0 1 As1);
+pick_first_impl!(3 0 As0 1 As1 2 As2);
+pick_first_impl!(4 0 As0 1 As1 2 As2 3 As3);
+
+impl<'de, T, TAs> DeserializeAs<'de, Vec<T>> for KeyValueMap<TAs>
+where
+    TAs: DeserializeAs<'de, T>,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<Vec<T>, D::Error>
     where
         D: Deserializer<'de>,
     {
-        crate::rust::display_fromstr::deserialize(deserializer).map(|secs| Duration::new(secs, 0))
+        struct KeyValueMapVisitor<T, TAs> {
+            is_human_readable: bool,
+            marker: PhantomData<(T, TAs)>,
+        }
+
+        impl<'de, T, TAs> Visitor<'de> for KeyValueMapVisitor<T, TAs>
+        where
+            TAs: DeserializeAs<'de, T>,
+        {
+            type Value = Vec<T>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut values = Vec::with_capacity(utils::size_hint_cautious(map.size_hint()));
+                while let Some(key) = map.next_key::<Content<'de>>()? {
+                    let value: DeserializeAsWrap<T, TAs> =
+                        map.next_value_seed(KeyValue::seed(key, self.is_human_readable))?;
+                    values.push(value.into_inner());
+                }
+                Ok(values)
+            }
+        }
+
+        let visitor = KeyValueMapVisitor::<T, TAs> {
+            is_human_readable: deserializer.is_human_readable(),
+            marker: PhantomData,
+        };
+        deserializer.deserialize_map(visitor)
     }
 }
 
-impl<'de, FORMAT> DeserializeAs<'de, Duration> for DurationSeconds<FORMAT, Flexible>
+impl<'de, T, TAs, PREFERENCE> DeserializeAs<'de, Vec<T>> for OneOrMany<TAs, PREFERENCE>
 where
-    FORMAT: Format,
+    TAs: DeserializeAs<'de, T>,
+    PREFERENCE: Preference,
 {
-    fn deserialize_as<D>(deserializer: D) -> Result<Duration, D::Error>
+    fn deserialize_as<D>(deserializer: D) -> Result<Vec<T>, D::Error>
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_any(DurationVisitiorFlexible)
+        let is_human_readable = deserializer.is_human_readable();
+        let content = Content::deserialize(deserializer)?;
+        let one_err = match TAs::deserialize_as(ContentRefDeserializer::<D::Error>::new(
+            &content,
+            is_human_readable,
+        )) {
+            Ok(one) => return Ok(vec![one]),
+            Err(err) => err,
+        };
+        let many_err = match Vec::<TAs>::deserialize_as(ContentRefDeserializer::<D::Error>::new(
+            &content,
+            is_human_readable,
+        )) {
+            Ok(many) => return Ok(many),
+            Err(err) => err,
+        };
+        Err(Error::custom(format_args!(
+            "OneOrMany could not deserialize data: as one element: {}; as a sequence: {}",
+            one_err, many_err
+        )))
     }
 }
 
-impl<'de> DeserializeAs<'de, Duration> for DurationSecondsWithFrac<f64, Strict> {
+use_duration_signed_de!(Duration, DurationSigned::to_std_duration, unsigned => DurationSeconds, DurationSecondsWithFrac, 1);
+use_duration_signed_de!(Duration, DurationSigned::to_std_duration, unsigned => DurationMilliSeconds, DurationMilliSecondsWithFrac, utils::MILLIS_PER_SEC);
+use_duration_signed_de!(Duration, DurationSigned::to_std_duration, unsigned => DurationMicroSeconds, DurationMicroSecondsWithFrac, utils::MICROS_PER_SEC);
+use_duration_signed_de!(Duration, DurationSigned::to_std_duration, unsigned => DurationNanoSeconds, DurationNanoSecondsWithFrac, utils::NANOS_PER_SEC);
+use_duration_signed_de!(SystemTime, DurationSigned::to_system_time => TimestampSeconds, TimestampSecondsWithFrac, 1);
+use_duration_signed_de!(SystemTime, DurationSigned::to_system_time => TimestampMilliSeconds, TimestampMilliSecondsWithFrac, utils::MILLIS_PER_SEC);
+use_duration_signed_de!(SystemTime, DurationSigned::to_system_time => TimestampMicroSeconds, TimestampMicroSecondsWithFrac, utils::MICROS_PER_SEC);
+use_duration_signed_de!(SystemTime, DurationSigned::to_system_time => TimestampNanoSeconds, TimestampNanoSecondsWithFrac, utils::NANOS_PER_SEC);
+
+impl<'de, DESIGNATORS> DeserializeAs<'de, Duration> for Iso8601Duration<DESIGNATORS>
+where
+    DESIGNATORS: Iso8601Designators,
+{
     fn deserialize_as<D>(deserializer: D) -> Result<Duration, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let val = f64::deserialize(deserializer)?;
-        utils::duration_from_secs_f64(val).map_err(Error::custom)
+        deserialize_iso8601_duration(deserializer, DESIGNATORS::DAYS_AND_WEEKS)?.to_std_duration()
     }
 }
 
-impl<'de> DeserializeAs<'de, Duration> for DurationSecondsWithFrac<String, Strict> {
+impl<'de> DeserializeAs<'de, Duration> for DurationHumanString<Strict> {
     fn deserialize_as<D>(deserializer: D) -> Result<Duration, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let dur = String::deserialize(deserializer)?;
-        DurationVisitiorFlexible.visit_str(&*dur)
+        deserializer
+            .deserialize_str(DurationHumanStringVisitor::new(false))?
+            .to_std_duration()
     }
 }
 
-impl<'de, FORMAT> DeserializeAs<'de, Duration> for DurationSecondsWithFrac<FORMAT, Flexible>
-where
-    FORMAT: Format,
-{
+impl<'de> DeserializeAs<'de, Duration> for DurationHumanString<Flexible> {
     fn deserialize_as<D>(deserializer: D) -> Result<Duration, D::Error>
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_any(DurationVisitiorFlexible)
+        deserializer
+            .deserialize_any(DurationHumanStringVisitor::new(true))?
+            .to_std_duration()
     }
 }
@@ -7,5 +7,21 @@ pub trait DeserializeAs<'de, T>: Sized {
     where
         D: Deserializer<'de>;
 
-    // TODO: deserialize_as_into
+    /// Deserialize into an existing value, reusing its allocations where possible.
+    ///
+    /// This mirrors [`Deserialize::deserialize_in_place`][serde::Deserialize::deserialize_in_place].
+    /// The default implementation deserializes a new value and overwrites `place` with it.
+    /// Collections override it to reuse their existing buffers.
+    ///
+    /// serde_derive never calls this function, not even for `#[serde(with = "...")]` fields.
+    /// It is only used when called directly, e.g., using [`As::deserialize_in_place`](crate::As::deserialize_in_place).
+    ///
+    /// If an error is returned, `place` is left in a valid but unspecified state.
+    fn deserialize_as_into<D>(deserializer: D, place: &mut T) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        *place = Self::deserialize_as(deserializer)?;
+        Ok(())
+    }
 }
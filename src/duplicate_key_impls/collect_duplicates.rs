@@ -0,0 +1,265 @@
+use serde::de::{Deserialize, MapAccess};
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fmt::{self, Debug, Display},
+    hash::{BuildHasher, Hash},
+    slice,
+};
+
+/// Map which hands back the entry, if the key already existed
+///
+/// This allows to continue reading the map and to collect all the duplicate keys in [`DuplicateKeys`].
+pub trait CollectDuplicateInsertsMap<K, V>: Sized {
+    /// Map from the keys to a position, used to group the occurrences of each duplicate key
+    ///
+    /// This is usually the same kind of map, storing `usize` values.
+    type Index: CollectDuplicateInsertsMap<K, usize>;
+
+    /// Create an empty map, which can hold `size_hint` many entries without reallocating
+    fn new(size_hint: Option<usize>) -> Self;
+
+    /// Return the value stored for the key
+    fn get(&self, key: &K) -> Option<&V>;
+
+    /// Insert the entry if the key did not exist in the map, otherwise return the entry
+    fn insert(&mut self, key: K, value: V) -> Result<(), (K, V)>;
+}
+
+impl<K, V, S> CollectDuplicateInsertsMap<K, V> for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    type Index = HashMap<K, usize, S>;
+
+    #[inline]
+    fn new(size_hint: Option<usize>) -> Self {
+        match size_hint {
+            Some(size) => Self::with_capacity_and_hasher(size, S::default()),
+            None => Self::with_hasher(S::default()),
+        }
+    }
+
+    #[inline]
+    fn get(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    #[inline]
+    fn insert(&mut self, key: K, value: V) -> Result<(), (K, V)> {
+        if self.contains_key(&key) {
+            return Err((key, value));
+        }
+        self.insert(key, value);
+        Ok(())
+    }
+}
+
+impl<K, V> CollectDuplicateInsertsMap<K, V> for BTreeMap<K, V>
+where
+    K: Ord,
+{
+    type Index = BTreeMap<K, usize>;
+
+    #[inline]
+    fn new(_size_hint: Option<usize>) -> Self {
+        Self::new()
+    }
+
+    #[inline]
+    fn get(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    #[inline]
+    fn insert(&mut self, key: K, value: V) -> Result<(), (K, V)> {
+        if self.contains_key(&key) {
+            return Err((key, value));
+        }
+        self.insert(key, value);
+        Ok(())
+    }
+}
+
+/// Read all entries of a map and collect the duplicate keys
+///
+/// Every entry is inserted into a new map of type `T`.
+/// If any key occurs more than once, all duplicate keys are returned as [`DuplicateKeys`] after the whole map was read.
+/// Other errors, e.g., for invalid values, are returned immediately.
+///
+/// This function is meant for hand-written [`Visitor::visit_map`](serde::de::Visitor::visit_map) implementations.
+/// It keeps the [`DuplicateKeys`] accessible, which [`rust::maps_report_all_duplicate_keys`](crate::rust::maps_report_all_duplicate_keys) can only report as string.
+pub fn collect_duplicate_keys<'de, A, T, K, V>(
+    mut access: A,
+) -> Result<Result<T, DuplicateKeys>, A::Error>
+where
+    A: MapAccess<'de>,
+    T: CollectDuplicateInsertsMap<K, V>,
+    K: Deserialize<'de> + Debug,
+    V: Deserialize<'de> + Debug,
+{
+    let mut values = <T as CollectDuplicateInsertsMap<K, V>>::new(access.size_hint());
+    let mut index = <T::Index as CollectDuplicateInsertsMap<K, usize>>::new(None);
+    let mut duplicates = DuplicateKeys::default();
+
+    while let Some((key, value)) = access.next_entry()? {
+        let (key, value) = match values.insert(key, value) {
+            Ok(()) => continue,
+            Err(entry) => entry,
+        };
+        let value = format!("{:?}", value);
+        match index.get(&key) {
+            Some(&position) => duplicates.keys[position].values.push(value),
+            None => {
+                let first = values
+                    .get(&key)
+                    .map(|first| format!("{:?}", first))
+                    .unwrap_or_default();
+                duplicates.keys.push(DuplicateKey {
+                    key: format!("{:?}", key),
+                    values: vec![first, value],
+                });
+                // The key cannot be in the index, since `get` just returned `None`
+                let _ = index.insert(key, duplicates.keys.len() - 1);
+            }
+        }
+    }
+
+    if duplicates.is_empty() {
+        Ok(Ok(values))
+    } else {
+        Ok(Err(duplicates))
+    }
+}
+
+/// All the keys which occurred more than once in a map
+///
+/// The keys are listed in the order of their first duplicate occurrence.
+/// Each key keeps the values of all its occurrences.
+/// Keys and values are stored using their [`Debug`] representation.
+///
+/// # Example
+///
+/// ```rust
+/// # use serde::de::{Deserializer, MapAccess, Visitor};
+/// # use serde_with::duplicate_key_impls::{collect_duplicate_keys, DuplicateKeys};
+/// # use std::{collections::BTreeMap, fmt};
+/// #
+/// struct DuplicatesVisitor;
+///
+/// impl<'de> Visitor<'de> for DuplicatesVisitor {
+///     type Value = Result<BTreeMap<String, u32>, DuplicateKeys>;
+///
+///     fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         formatter.write_str("a map")
+///     }
+///
+///     fn visit_map<A: MapAccess<'de>>(self, access: A) -> Result<Self::Value, A::Error> {
+///         collect_duplicate_keys(access)
+///     }
+/// }
+///
+/// let mut deserializer = serde_json::Deserializer::from_str(r#"{"a":1,"b":2,"a":3,"b":4,"a":5}"#);
+/// let duplicates = deserializer.deserialize_map(DuplicatesVisitor).unwrap().unwrap_err();
+///
+/// let summary: Vec<_> = duplicates
+///     .iter()
+///     .map(|duplicate| (duplicate.key(), duplicate.values()))
+///     .collect();
+/// assert_eq!(
+///     vec![
+///         (r#""a""#, &["1".to_string(), "3".to_string(), "5".to_string()][..]),
+///         (r#""b""#, &["2".to_string(), "4".to_string()][..]),
+///     ],
+///     summary,
+/// );
+/// assert_eq!(
+///     r#"invalid entries: found 2 duplicate keys: "a" with 3 values: 1, 3, 5; "b" with 2 values: 2, 4"#,
+///     duplicates.to_string(),
+/// );
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DuplicateKeys {
+    keys: Vec<DuplicateKey>,
+}
+
+impl DuplicateKeys {
+    /// Return `true` if no duplicate keys were found
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Number of distinct keys which occurred more than once
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Iterate over the duplicate keys
+    pub fn iter(&self) -> slice::Iter<'_, DuplicateKey> {
+        self.keys.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a DuplicateKeys {
+    type Item = &'a DuplicateKey;
+    type IntoIter = slice::Iter<'a, DuplicateKey>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Display for DuplicateKeys {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "invalid entries: found {} duplicate key{}",
+            self.keys.len(),
+            if self.keys.len() == 1 { "" } else { "s" }
+        )?;
+        for (i, duplicate) in self.keys.iter().enumerate() {
+            let separator = if i == 0 { ": " } else { "; " };
+            write!(formatter, "{}{}", separator, duplicate)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for DuplicateKeys {}
+
+/// A key which occurred more than once in a map, together with the values of all its occurrences
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DuplicateKey {
+    key: String,
+    values: Vec<String>,
+}
+
+impl DuplicateKey {
+    /// [`Debug`] representation of the key
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// [`Debug`] representation of the values, in the order in which they occurred
+    pub fn values(&self) -> &[String] {
+        &self.values
+    }
+
+    /// Number of occurrences of the key
+    pub fn count(&self) -> usize {
+        self.values.len()
+    }
+}
+
+impl Display for DuplicateKey {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "{} with {} values: {}",
+            self.key,
+            self.values.len(),
+            self.values.join(", ")
+        )
+    }
+}
@@ -0,0 +1,39 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// Set with a fixed value type
+///
+/// The `serde_as` adapters, like [`SetPreventDuplicates`](crate::SetPreventDuplicates), use it to determine the value type.
+/// Implement it together with the insertion traits to use the adapters with other set types.
+pub trait SetValue {
+    /// Type of the values stored in the set
+    type Value;
+}
+
+/// Map with fixed key and value types
+///
+/// The `serde_as` adapters, like [`MapFirstKeyWins`](crate::MapFirstKeyWins), use it to determine the key and value types.
+/// Implement it together with the insertion traits to use the adapters with other map types.
+pub trait MapEntry {
+    /// Type of the keys stored in the map
+    type Key;
+    /// Type of the values stored in the map
+    type Value;
+}
+
+impl<T, S> SetValue for HashSet<T, S> {
+    type Value = T;
+}
+
+impl<T> SetValue for BTreeSet<T> {
+    type Value = T;
+}
+
+impl<K, V, S> MapEntry for HashMap<K, V, S> {
+    type Key = K;
+    type Value = V;
+}
+
+impl<K, V> MapEntry for BTreeMap<K, V> {
+    type Key = K;
+    type Value = V;
+}
@@ -3,14 +3,18 @@ use std::{
     hash::{BuildHasher, Hash},
 };
 
+/// Set which reports if an inserted value already existed
 pub trait PreventDuplicateInsertsSet<T> {
+    /// Create an empty set, which can hold `size_hint` many elements without reallocating
     fn new(size_hint: Option<usize>) -> Self;
 
     /// Return true if the insert was successful and the value did not exist in the set
     fn insert(&mut self, value: T) -> bool;
 }
 
+/// Map which reports if an inserted key already existed
 pub trait PreventDuplicateInsertsMap<K, V> {
+    /// Create an empty map, which can hold `size_hint` many entries without reallocating
     fn new(size_hint: Option<usize>) -> Self;
 
     /// Return true if the insert was successful and the key did not exist in the map
@@ -3,14 +3,18 @@ use std::{
     hash::{BuildHasher, Hash},
 };
 
+/// Set which keeps the first of several equal values
 pub trait DuplicateInsertsFirstWinsSet<T> {
+    /// Create an empty set, which can hold `size_hint` many elements without reallocating
     fn new(size_hint: Option<usize>) -> Self;
 
     /// Insert the value into the set, if there is not already an existing value
     fn insert(&mut self, value: T);
 }
 
+/// Map which keeps the value of the first occurrence of a key
 pub trait DuplicateInsertsFirstWinsMap<K, V> {
+    /// Create an empty map, which can hold `size_hint` many entries without reallocating
     fn new(size_hint: Option<usize>) -> Self;
 
     /// Insert the value into the map, if there is not already an existing value
@@ -0,0 +1,49 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    hash::{BuildHasher, Hash},
+};
+
+/// Set which replaces an existing equal value on insert, keeping the last value
+pub trait DuplicateInsertsLastWinsSet<T> {
+    /// Create an empty set, which can hold `size_hint` many elements without reallocating
+    fn new(size_hint: Option<usize>) -> Self;
+
+    /// Insert or replace the existing value
+    fn replace(&mut self, value: T);
+}
+
+impl<T, S> DuplicateInsertsLastWinsSet<T> for HashSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    #[inline]
+    fn new(size_hint: Option<usize>) -> Self {
+        match size_hint {
+            Some(size) => Self::with_capacity_and_hasher(size, S::default()),
+            None => Self::with_hasher(S::default()),
+        }
+    }
+
+    #[inline]
+    fn replace(&mut self, value: T) {
+        // Hashset keeps the existing value on insert, so it needs to be replaced explicitly
+        self.replace(value);
+    }
+}
+
+impl<T> DuplicateInsertsLastWinsSet<T> for BTreeSet<T>
+where
+    T: Ord,
+{
+    #[inline]
+    fn new(_size_hint: Option<usize>) -> Self {
+        Self::new()
+    }
+
+    #[inline]
+    fn replace(&mut self, value: T) {
+        // BTreeSet keeps the existing value on insert, so it needs to be replaced explicitly
+        self.replace(value);
+    }
+}
@@ -1,7 +1,30 @@
+//! Insertion strategies for duplicate keys and values in maps and sets
+//!
+//! The traits in this module are used by the duplicate handling in [`rust`](crate::rust) and by the
+//! [`MapPreventDuplicates`](crate::MapPreventDuplicates), [`MapFirstKeyWins`](crate::MapFirstKeyWins),
+//! [`SetPreventDuplicates`](crate::SetPreventDuplicates), and [`SetLastValueWins`](crate::SetLastValueWins) adapters.
+//! They are implemented for the maps and sets of the standard library
+//! and can be implemented for other collection types.
+//! The `rust` modules only require the insertion trait.
+//! The adapters additionally require [`SetValue`] or [`MapEntry`], which name the element types,
+//! and iterating over a reference of the collection for serialization.
+//!
+//! [`CollectDuplicateInsertsMap`], [`collect_duplicate_keys`], and [`DuplicateKeys`] are used by
+//! [`rust::maps_report_all_duplicate_keys`](crate::rust::maps_report_all_duplicate_keys),
+//! which reads the whole map and reports all duplicate keys at once.
+
+mod collect_duplicates;
+mod element_types;
 mod error_on_duplicate;
 mod first_value_wins;
+mod last_value_wins;
 
 pub use self::{
+    collect_duplicates::{
+        collect_duplicate_keys, CollectDuplicateInsertsMap, DuplicateKey, DuplicateKeys,
+    },
+    element_types::{MapEntry, SetValue},
     error_on_duplicate::{PreventDuplicateInsertsMap, PreventDuplicateInsertsSet},
     first_value_wins::{DuplicateInsertsFirstWinsMap, DuplicateInsertsFirstWinsSet},
+    last_value_wins::DuplicateInsertsLastWinsSet,
 };
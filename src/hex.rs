@@ -1,25 +1,159 @@
-use crate::{de::DeserializeAs, ser::SerializeAs};
-use serde::{Deserialize, Deserializer, Serializer};
+//! De/Serialization of hexadecimal encoded bytes
+//!
+//! This modules is only available if using the `hex` feature of the crate.
 
+use crate::{
+    de::DeserializeAs,
+    ser::SerializeAs,
+    utils::bytes::{deserialize_decoded, serialize_encoded},
+};
+use hex_crate::FromHexError;
+use serde::{Deserializer, Serializer};
+use std::{convert::TryFrom, marker::PhantomData};
+
+/// Serialize bytes as a hex string
+///
+/// The type supports the same byte types as all [byte encodings](crate#byte-encodings), like `Vec<u8>` and `[u8; N]`.
+///
+/// The [`HexCase`] selects the letters used while serializing, [`Lowercase`] is the default.
+/// Deserialization accepts both lowercase and uppercase letters.
+/// The [`HexPrefix`] selects whether the digits are prefixed by `0x`.
+/// With [`Prefixed`] the prefix is written while serializing and required while deserializing, where `0X` is accepted too.
+///
+/// # Example
+///
+/// ```rust
+/// # use serde_derive::{Deserialize, Serialize};
+/// # use serde_with::{hex::{Hex, Lowercase, Prefixed, Uppercase}, As};
+/// #
+/// #[derive(Deserialize, Serialize)]
+/// struct Payload {
+///     #[serde(with = "As::<Hex>")]
+///     lower: Vec<u8>,
+///     #[serde(with = "As::<Hex<Uppercase>>")]
+///     upper: Vec<u8>,
+///     #[serde(with = "As::<Hex<Lowercase, Prefixed>>")]
+///     address: Vec<u8>,
+///     #[serde(with = "As::<Hex<Uppercase, Prefixed>>")]
+///     checksum: Vec<u8>,
+/// }
+///
+/// let payload: Payload = serde_json::from_str(
+///     r#"{"lower":"deadbeef","upper":"DEADBEEF","address":"0xdeadbeef","checksum":"0XCAFE"}"#,
+/// ).unwrap();
+/// assert_eq!(vec![0xde, 0xad, 0xbe, 0xef], payload.address);
+/// assert_eq!(
+///     r#"{"lower":"deadbeef","upper":"DEADBEEF","address":"0xdeadbeef","checksum":"0xCAFE"}"#,
+///     serde_json::to_string(&payload).unwrap(),
+/// );
+/// ```
 #[derive(Copy, Clone, Debug, Default)]
-pub struct Hex;
+pub struct Hex<CASE: HexCase = Lowercase, PREFIX: HexPrefix = Unprefixed>(
+    PhantomData<(CASE, PREFIX)>,
+);
+
+/// Letter case of the digits `a` to `f`, written by the [`Hex`] adapter
+pub trait HexCase {
+    /// Use uppercase letters for the digits `a` to `f`
+    const UPPERCASE: bool;
+}
+
+/// Lowercase hex digits, like `deadbeef`
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Lowercase;
+
+impl HexCase for Lowercase {
+    const UPPERCASE: bool = false;
+}
+
+/// Uppercase hex digits, like `DEADBEEF`
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Uppercase;
+
+impl HexCase for Uppercase {
+    const UPPERCASE: bool = true;
+}
+
+/// Prefix of the hex digits, used by the [`Hex`] adapter
+pub trait HexPrefix {
+    /// Write the `0x` prefix while serializing and require `0x` or `0X` while deserializing
+    const PREFIX: bool;
+}
+
+/// The digits are prefixed by `0x`, like `0xdeadbeef`
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Prefixed;
+
+impl HexPrefix for Prefixed {
+    const PREFIX: bool = true;
+}
+
+/// The digits are not prefixed, like `deadbeef`
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Unprefixed;
+
+impl HexPrefix for Unprefixed {
+    const PREFIX: bool = false;
+}
 
-// TODO: AsRef
-impl SerializeAs<Vec<u8>> for Hex {
-    fn serialize_as<S>(source: &Vec<u8>, serializer: S) -> Result<S::Ok, S::Error>
+impl<T, CASE, PREFIX> SerializeAs<T> for Hex<CASE, PREFIX>
+where
+    T: AsRef<[u8]> + ?Sized,
+    CASE: HexCase,
+    PREFIX: HexPrefix,
+{
+    fn serialize_as<S>(source: &T, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        serializer.serialize_str(&::hex::encode(source))
+        serialize_encoded(
+            source,
+            |bytes| {
+                let mut encoded = String::new();
+                if PREFIX::PREFIX {
+                    encoded.push_str("0x");
+                }
+                if CASE::UPPERCASE {
+                    encoded.push_str(&hex_crate::encode_upper(bytes));
+                } else {
+                    encoded.push_str(&hex_crate::encode(bytes));
+                }
+                encoded
+            },
+            serializer,
+        )
     }
 }
 
-impl<'de> DeserializeAs<'de, Vec<u8>> for Hex {
-    fn deserialize_as<D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+impl<'de, T, CASE, PREFIX> DeserializeAs<'de, T> for Hex<CASE, PREFIX>
+where
+    T: TryFrom<Vec<u8>>,
+    CASE: HexCase,
+    PREFIX: HexPrefix,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<T, D::Error>
     where
         D: Deserializer<'de>,
     {
-        // FIXME: map decode errors
-        <&'de str as Deserialize<'de>>::deserialize(deserializer).map(|s| ::hex::decode(s).unwrap())
+        deserialize_decoded(deserializer, |value| {
+            let digits = if PREFIX::PREFIX {
+                if !value.starts_with("0x") && !value.starts_with("0X") {
+                    return Err("Invalid hex string: expected the prefix '0x'".into());
+                }
+                &value[2..]
+            } else {
+                value
+            };
+            let offset = value.len() - digits.len();
+            hex_crate::decode(digits).map_err(|err| match err {
+                FromHexError::InvalidHexCharacter { c, index } => format!(
+                    "Invalid hex string: invalid character {:?} at offset {}",
+                    c,
+                    offset + index
+                ),
+                FromHexError::OddLength => "Invalid hex string: odd number of digits".into(),
+                err => format!("Invalid hex string: {}", err),
+            })
+        })
     }
 }
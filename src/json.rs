@@ -2,8 +2,13 @@
 //!
 //! This modules is only available if using the `json` feature of the crate.
 
-use crate::{de::DeserializeAs, ser::SerializeAs};
+use crate::{de::DeserializeAs, ser::SerializeAs, Same};
 use serde::{de::DeserializeOwned, Deserializer, Serialize, Serializer};
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+};
 
 /// Serialize value as string containing JSON
 ///
@@ -110,3 +115,88 @@ where
         crate::json::nested::deserialize(deserializer)
     }
 }
+
+/// De/Serialize the keys of a map as strings containing JSON
+///
+/// JSON only allows strings as keys of objects.
+/// This adapter keeps the object shape for other keys, like tuples or structs, by encoding every key as [`JsonString`].
+/// It works for `BTreeMap` and `HashMap`, and the values are de/serialized using `VAs`.
+/// The adapter is a shorthand for `BTreeMap<JsonString, VAs>` or `HashMap<JsonString, VAs>`.
+///
+/// Keys which are strings themselves are encoded as JSON strings too, i.e., they get quoted.
+/// Use [`MapKeysDisplayFromStr`](crate::MapKeysDisplayFromStr) for keys with a natural string representation.
+///
+/// # Example
+///
+/// ```rust
+/// # use serde_derive::{Deserialize, Serialize};
+/// # use serde_with::{json::MapKeysAsJsonString, As};
+/// # use std::collections::BTreeMap;
+/// #
+/// #[derive(Deserialize, Serialize)]
+/// struct Grid {
+///     #[serde(with = "As::<MapKeysAsJsonString>")]
+///     cells: BTreeMap<(u32, u32), char>,
+/// }
+///
+/// let mut cells = BTreeMap::new();
+/// cells.insert((0, 1), 'x');
+/// let grid = Grid { cells };
+/// assert_eq!(r#"{"cells":{"[0,1]":"x"}}"#, serde_json::to_string(&grid).unwrap());
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct MapKeysAsJsonString<VAs = Same>(PhantomData<VAs>);
+
+impl<K, V, VAs> SerializeAs<BTreeMap<K, V>> for MapKeysAsJsonString<VAs>
+where
+    K: Serialize + Ord,
+    VAs: SerializeAs<V>,
+{
+    fn serialize_as<S>(source: &BTreeMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        BTreeMap::<JsonString, VAs>::serialize_as(source, serializer)
+    }
+}
+
+impl<K, V, VAs, H> SerializeAs<HashMap<K, V, H>> for MapKeysAsJsonString<VAs>
+where
+    K: Serialize + Eq + Hash,
+    VAs: SerializeAs<V>,
+    H: BuildHasher,
+{
+    fn serialize_as<S>(source: &HashMap<K, V, H>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        HashMap::<JsonString, VAs, H>::serialize_as(source, serializer)
+    }
+}
+
+impl<'de, K, V, VAs> DeserializeAs<'de, BTreeMap<K, V>> for MapKeysAsJsonString<VAs>
+where
+    K: DeserializeOwned + Ord,
+    VAs: DeserializeAs<'de, V>,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<BTreeMap<K, V>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        BTreeMap::<JsonString, VAs>::deserialize_as(deserializer)
+    }
+}
+
+impl<'de, K, V, VAs, S> DeserializeAs<'de, HashMap<K, V, S>> for MapKeysAsJsonString<VAs>
+where
+    K: DeserializeOwned + Eq + Hash,
+    VAs: DeserializeAs<'de, V>,
+    S: BuildHasher + Default,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<HashMap<K, V, S>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        HashMap::<JsonString, VAs, S>::deserialize_as(deserializer)
+    }
+}
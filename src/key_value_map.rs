@@ -0,0 +1,786 @@
+//! Intercepting Serializer and Deserializer for [`KeyValueMap`](crate::KeyValueMap)
+//!
+//! Serialization runs twice over every element.
+//! The first pass only emits the key, i.e., the first field, as map key.
+//! The second pass emits the element without its first field as map value.
+//!
+//! Deserialization wraps the deserializer of the map value and reinserts the buffered map key as first field.
+//! This follows the same approach as [`WithPrefix`](crate::with_prefix::WithPrefix).
+
+use crate::utils::content::{Content, ContentRefDeserializer};
+use serde::{
+    de::{self, DeserializeSeed, Deserializer, IntoDeserializer, MapAccess, SeqAccess, Visitor},
+    forward_to_deserialize_any,
+    ser::{
+        self, Impossible, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeTuple,
+        SerializeTupleStruct, Serializer,
+    },
+    Deserialize,
+};
+use std::{fmt, marker::PhantomData};
+
+/// Name of the struct field holding the key
+pub(crate) const KEY_FIELD: &str = "$key$";
+
+fn unsupported<E: ser::Error>() -> E {
+    E::custom("KeyValueMap only supports structs, tuples, and sequences as elements")
+}
+
+/// Serialize the first field of `element` as the next key of `map`
+pub(crate) fn serialize_key<M, T>(map: &mut M, element: &T) -> Result<(), M::Error>
+where
+    M: SerializeMap,
+    T: Serialize + ?Sized,
+{
+    element.serialize(KeySerializer { map, found: false })
+}
+
+struct KeySerializer<'a, M> {
+    map: &'a mut M,
+    found: bool,
+}
+
+impl<'a, M> KeySerializer<'a, M>
+where
+    M: SerializeMap,
+{
+    fn serialize_first<T>(&mut self, value: &T) -> Result<(), M::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        if !self.found {
+            self.found = true;
+            self.map.serialize_key(value)?;
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), M::Error> {
+        if self.found {
+            Ok(())
+        } else {
+            Err(ser::Error::custom(
+                "KeyValueMap requires elements with at least one field",
+            ))
+        }
+    }
+}
+
+impl<'a, M> Serializer for KeySerializer<'a, M>
+where
+    M: SerializeMap,
+{
+    type Ok = ();
+    type Error = M::Error;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Impossible<Self::Ok, Self::Error>;
+    type SerializeMap = Impossible<Self::Ok, Self::Error>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Impossible<Self::Ok, Self::Error>;
+
+    fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_i8(self, _v: i8) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_i16(self, _v: i16) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_i32(self, _v: i32) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_i64(self, _v: i64) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_u8(self, _v: u8) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_u16(self, _v: u16) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_u32(self, _v: u32) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_u64(self, _v: u64) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_char(self, _v: char) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_str(self, _v: &str) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_some<T>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(unsupported())
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(unsupported())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(unsupported())
+    }
+}
+
+impl<'a, M> SerializeSeq for KeySerializer<'a, M>
+where
+    M: SerializeMap,
+{
+    type Ok = ();
+    type Error = M::Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.serialize_first(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl<'a, M> SerializeTuple for KeySerializer<'a, M>
+where
+    M: SerializeMap,
+{
+    type Ok = ();
+    type Error = M::Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.serialize_first(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl<'a, M> SerializeTupleStruct for KeySerializer<'a, M>
+where
+    M: SerializeMap,
+{
+    type Ok = ();
+    type Error = M::Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.serialize_first(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl<'a, M> SerializeStruct for KeySerializer<'a, M>
+where
+    M: SerializeMap,
+{
+    type Ok = ();
+    type Error = M::Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        if !self.found && key != KEY_FIELD {
+            return Err(ser::Error::custom(format_args!(
+                "KeyValueMap requires the first field to be named `{}`, but found `{}`",
+                KEY_FIELD, key
+            )));
+        }
+        self.serialize_first(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+/// Serialize the wrapped element without its first field
+pub(crate) struct WithoutKey<'a, T: ?Sized>(pub(crate) &'a T);
+
+impl<'a, T> Serialize for WithoutKey<'a, T>
+where
+    T: Serialize + ?Sized,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(SkipFirst {
+            delegate: serializer,
+            skipped: false,
+        })
+    }
+}
+
+struct SkipFirst<S> {
+    delegate: S,
+    skipped: bool,
+}
+
+impl<S> SkipFirst<S> {
+    fn wrap(delegate: S) -> Self {
+        SkipFirst {
+            delegate,
+            skipped: false,
+        }
+    }
+
+    /// Return `true` for the first call
+    fn skip(&mut self) -> bool {
+        !std::mem::replace(&mut self.skipped, true)
+    }
+}
+
+impl<S> Serializer for SkipFirst<S>
+where
+    S: Serializer,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = SkipFirst<S::SerializeSeq>;
+    type SerializeTuple = SkipFirst<S::SerializeTuple>;
+    type SerializeTupleStruct = SkipFirst<S::SerializeTupleStruct>;
+    type SerializeTupleVariant = Impossible<Self::Ok, Self::Error>;
+    type SerializeMap = Impossible<Self::Ok, Self::Error>;
+    type SerializeStruct = SkipFirst<S::SerializeStruct>;
+    type SerializeStructVariant = Impossible<Self::Ok, Self::Error>;
+
+    fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_i8(self, _v: i8) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_i16(self, _v: i16) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_i32(self, _v: i32) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_i64(self, _v: i64) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_u8(self, _v: u8) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_u16(self, _v: u16) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_u32(self, _v: u32) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_u64(self, _v: u64) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_char(self, _v: char) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_str(self, _v: &str) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_some<T>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(unsupported())
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(unsupported())
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        let len = len.map(|len| len.saturating_sub(1));
+        Ok(SkipFirst::wrap(self.delegate.serialize_seq(len)?))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Ok(SkipFirst::wrap(
+            self.delegate.serialize_tuple(len.saturating_sub(1))?,
+        ))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Ok(SkipFirst::wrap(
+            self.delegate
+                .serialize_tuple_struct(name, len.saturating_sub(1))?,
+        ))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(SkipFirst::wrap(
+            self.delegate
+                .serialize_struct(name, len.saturating_sub(1))?,
+        ))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(unsupported())
+    }
+}
+
+impl<S> SerializeSeq for SkipFirst<S>
+where
+    S: SerializeSeq,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        if self.skip() {
+            return Ok(());
+        }
+        self.delegate.serialize_element(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.delegate.end()
+    }
+}
+
+impl<S> SerializeTuple for SkipFirst<S>
+where
+    S: SerializeTuple,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        if self.skip() {
+            return Ok(());
+        }
+        self.delegate.serialize_element(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.delegate.end()
+    }
+}
+
+impl<S> SerializeTupleStruct for SkipFirst<S>
+where
+    S: SerializeTupleStruct,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        if self.skip() {
+            return Ok(());
+        }
+        self.delegate.serialize_field(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.delegate.end()
+    }
+}
+
+impl<S> SerializeStruct for SkipFirst<S>
+where
+    S: SerializeStruct,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        if self.skip() {
+            return Ok(());
+        }
+        self.delegate.serialize_field(key, value)
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<(), Self::Error> {
+        self.delegate.skip_field(key)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.delegate.end()
+    }
+}
+
+/// Reinsert the buffered `key` as first field while deserializing
+pub(crate) struct KeyValue<'de, T> {
+    key: Option<Content<'de>>,
+    delegate: T,
+    is_human_readable: bool,
+}
+
+impl<'de, T> KeyValue<'de, PhantomData<T>> {
+    /// Create a seed which deserializes a map value into a `T` with the `key` as first field
+    pub(crate) fn seed(key: Content<'de>, is_human_readable: bool) -> Self {
+        KeyValue {
+            key: Some(key),
+            delegate: PhantomData,
+            is_human_readable,
+        }
+    }
+}
+
+impl<'de, T> DeserializeSeed<'de> for KeyValue<'de, PhantomData<T>>
+where
+    T: Deserialize<'de>,
+{
+    type Value = T;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize(KeyValue {
+            key: self.key,
+            delegate: deserializer,
+            is_human_readable: self.is_human_readable,
+        })
+    }
+}
+
+impl<'de, D> Deserializer<'de> for KeyValue<'de, D>
+where
+    D: Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.delegate.deserialize_any(KeyValue {
+            key: self.key,
+            delegate: visitor,
+            is_human_readable: self.is_human_readable,
+        })
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.is_human_readable
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de, V> Visitor<'de> for KeyValue<'de, V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.delegate.expecting(formatter)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        self.delegate.visit_seq(KeyValue {
+            key: self.key,
+            delegate: seq,
+            is_human_readable: self.is_human_readable,
+        })
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        self.delegate.visit_map(KeyValue {
+            key: self.key,
+            delegate: map,
+            is_human_readable: self.is_human_readable,
+        })
+    }
+}
+
+impl<'de, A> SeqAccess<'de> for KeyValue<'de, A>
+where
+    A: SeqAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.key.take() {
+            Some(key) => seed
+                .deserialize(ContentRefDeserializer::new(&key, self.is_human_readable))
+                .map(Some),
+            None => self.delegate.next_element_seed(seed),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        let key = if self.key.is_some() { 1 } else { 0 };
+        self.delegate.size_hint().map(|len| len + key)
+    }
+}
+
+impl<'de, A> MapAccess<'de> for KeyValue<'de, A>
+where
+    A: MapAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        // The key stays buffered until `next_value_seed` consumes it
+        if self.key.is_some() {
+            let field: de::value::StrDeserializer<'_, A::Error> = KEY_FIELD.into_deserializer();
+            return seed.deserialize(field).map(Some);
+        }
+        self.delegate.next_key_seed(seed)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        match self.key.take() {
+            Some(key) => {
+                seed.deserialize(ContentRefDeserializer::new(&key, self.is_human_readable))
+            }
+            None => self.delegate.next_value_seed(seed),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        let key = if self.key.is_some() { 1 } else { 0 };
+        self.delegate.size_hint().map(|len| len + key)
+    }
+}
@@ -86,24 +86,65 @@
 //! The crate comes with custom attributes, which futher extend how serde serialization can be customized.
 //! They are enabled by default, but can be disabled, by removing the default features from this crate.
 //!
+//! The [`serde_as`] attribute makes it easy to use the [`As`] adapter types.
+//! Instead of the stringly-typed `#[serde(with = "As::<Vec<DisplayFromStr>>")]` the type can be written as `#[serde_as(as = "Vec<DisplayFromStr>")]`.
+//!
+//! ```rust
+//! # #[cfg(feature = "macros")]
+//! # use serde_derive::{Deserialize, Serialize};
+//! # #[cfg(feature = "macros")]
+//! use serde_with::{serde_as, DisplayFromStr};
+//!
+//! # #[cfg(feature = "macros")]
+//! #[serde_as]
+//! #[derive(Deserialize, Serialize)]
+//! struct Foo {
+//!     #[serde_as(as = "Vec<DisplayFromStr>")]
+//!     bar: Vec<u8>,
+//!     #[serde_as(as = "Option<DisplayFromStr>")]
+//!     baz: Option<u8>,
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! # Byte encodings
+//!
+//! The text encodings of bytes, like `hex::Hex`, `base64::Base64`, `base32::Base32`, and `base58::Base58`, all support the same types.
+//! They serialize anything which implements `AsRef<[u8]>`, like `Vec<u8>`, `[u8; N]`, `Box<[u8]>`, or `Cow<[u8]>`.
+//! They deserialize into any type which implements `TryFrom<Vec<u8>>`.
+//! Deserializing into `[u8; N]` requires Rust 1.48 and fails if the number of bytes does not match.
+//!
+//! [`serde_as`]: https://docs.rs/serde_with_macros/*/serde_with_macros/attr.serde_as.html
 //! [with-annotation]: https://serde.rs/field-attrs.html#with
 //! [serde#553]: https://github.com/serde-rs/serde/issues/553
 
 #[doc(hidden)]
 pub extern crate serde;
 
+// Needs to be declared first, such that the macros are available in all other modules
+#[macro_use]
+mod utils;
+#[cfg(feature = "base32")]
+pub mod base32;
+#[cfg(feature = "base58")]
+pub mod base58;
+#[cfg(feature = "base64")]
+pub mod base64;
 #[cfg(feature = "chrono")]
 pub mod chrono;
 pub mod de;
-mod duplicate_key_impls;
+pub mod duplicate_key_impls;
 mod flatten_maybe;
 #[cfg(feature = "hex")]
 pub mod hex;
 #[cfg(feature = "json")]
 pub mod json;
+mod key_value_map;
 pub mod rust;
+pub mod sentinel;
 pub mod ser;
-mod utils;
+#[cfg(feature = "time")]
+pub mod time;
 #[doc(hidden)]
 pub mod with_prefix;
 
@@ -113,7 +154,7 @@ use serde::{ser::Serialize, Deserializer, Serializer};
 #[cfg(feature = "macros")]
 #[doc(inline)]
 pub use serde_with_macros::*;
-use std::marker::PhantomData;
+use std::{fmt, marker::PhantomData};
 
 /// Separator for string-based collection de/serialization
 pub trait Separator {
@@ -143,6 +184,52 @@ impl Separator for CommaSeparator {
     }
 }
 
+/// Values which represent a missing value, used by [`NoneAs`]
+///
+/// The [`sentinel`] module contains predefined sentinels.
+pub trait Sentinel {
+    /// Return the values which are deserialized as `None`
+    ///
+    /// The first value is the primary sentinel, which is used to serialize `None`.
+    fn sentinels() -> &'static [SentinelValue];
+}
+
+/// A single value recognized by a [`Sentinel`]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum SentinelValue {
+    /// A string, like `"N/A"`
+    Str(&'static str),
+    /// An integer, like `0` or `-1`, matching any integer type
+    Int(i64),
+}
+
+/// Callback for the elements skipped by [`VecSkipError`] and [`MapSkipError`]
+///
+/// The adapters are only used as types, so the hook never has access to an instance and cannot carry any state.
+/// Counting or collecting the skipped elements therefore requires global state, like a `static` or a `thread_local!`.
+/// This state is shared by all fields and all deserializations which use the same hook type.
+pub trait SkipErrorHook {
+    /// Called with the position and the deserialization error of every skipped element
+    ///
+    /// The `index` counts all elements of the sequence or all entries of the map, including the skipped ones.
+    fn skipped(index: usize, error: &dyn fmt::Display);
+}
+
+/// Predefined hook which ignores skipped elements
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct IgnoreSkipped;
+
+impl SkipErrorHook for IgnoreSkipped {
+    #[inline]
+    fn skipped(_index: usize, _error: &dyn fmt::Display) {}
+}
+
+/// Adapter to convert from `serde_as` to the serde traits.
+///
+/// This is the type to use in `#[serde(with = "...")]` annotations, e.g., `#[serde(with = "As::<DisplayFromStr>")]`.
+/// The [`serde_as`] attribute generates these annotations automatically.
+///
+/// [`serde_as`]: https://docs.rs/serde_with_macros/*/serde_with_macros/attr.serde_as.html
 #[derive(Copy, Clone, Debug, Default)]
 pub struct As<T>(PhantomData<T>);
 
@@ -162,6 +249,35 @@ impl<T> As<T> {
     {
         T::deserialize_as(deserializer)
     }
+
+    /// Deserialize into an existing value, reusing its allocations where possible.
+    ///
+    /// See [`DeserializeAs::deserialize_as_into`] for details.
+    ///
+    /// serde_derive does not call this function for fields annotated with `#[serde(with = "As::<...>")]`.
+    /// Such fields always deserialize a new value, which replaces the old one.
+    /// The allocations are only reused if this function is called directly.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use serde_with::{As, DisplayFromStr};
+    /// #
+    /// let mut values: Vec<u32> = Vec::with_capacity(100);
+    /// let ptr = values.as_ptr();
+    ///
+    /// let mut deserializer = serde_json::Deserializer::from_str(r#"["1","2","3"]"#);
+    /// As::<Vec<DisplayFromStr>>::deserialize_in_place(&mut deserializer, &mut values).unwrap();
+    /// assert_eq!(vec![1, 2, 3], values);
+    /// assert_eq!(ptr, values.as_ptr());
+    /// ```
+    pub fn deserialize_in_place<'de, D, I>(deserializer: D, place: &mut I) -> Result<(), D::Error>
+    where
+        T: DeserializeAs<'de, I>,
+        D: Deserializer<'de>,
+    {
+        T::deserialize_as_into(deserializer, place)
+    }
 }
 
 #[derive(Copy, Clone, Debug, Default)]
@@ -173,15 +289,542 @@ pub struct SameAs<T>(PhantomData<T>);
 #[derive(Copy, Clone, Debug, Default)]
 pub struct DisplayFromStr;
 
+/// De/Serialize the keys of a map using [`DisplayFromStr`]
+///
+/// This keeps the map shape for keys which are not strings, like integers, IP addresses, or UUIDs.
+/// It works for `BTreeMap` and `HashMap`, and the values are de/serialized using `VAs`.
+/// The adapter is a shorthand for `BTreeMap<DisplayFromStr, VAs>` or `HashMap<DisplayFromStr, VAs>`.
+///
+/// Use [`json::MapKeysAsJsonString`] for keys without a `Display` and `FromStr` implementation.
+/// It requires the `json` feature.
+///
+/// # Example
+///
+/// ```rust
+/// # use serde_derive::{Deserialize, Serialize};
+/// # use serde_with::{As, MapKeysDisplayFromStr};
+/// # use std::{collections::BTreeMap, net::Ipv4Addr};
+/// #
+/// #[derive(Deserialize, Serialize)]
+/// struct Hosts {
+///     #[serde(with = "As::<MapKeysDisplayFromStr>")]
+///     names: BTreeMap<Ipv4Addr, String>,
+/// }
+///
+/// let mut names = BTreeMap::new();
+/// names.insert(Ipv4Addr::new(127, 0, 0, 1), "localhost".to_string());
+/// let hosts = Hosts { names };
+/// assert_eq!(
+///     r#"{"names":{"127.0.0.1":"localhost"}}"#,
+///     serde_json::to_string(&hosts).unwrap(),
+/// );
+/// ```
+///
+/// [`json::MapKeysAsJsonString`]: https://docs.rs/serde_with/*/serde_with/json/struct.MapKeysAsJsonString.html
+#[derive(Copy, Clone, Debug, Default)]
+pub struct MapKeysDisplayFromStr<VAs = Same>(PhantomData<VAs>);
+
+/// De/Serialize an `Option<String>` using the empty string for `None`
+///
+/// This behaves like [`NoneAs`] with the [`sentinel::EmptyString`] sentinel and shares its serialization.
+/// Deserialization reads a string directly instead of buffering the input, so it also works with formats which are not self-describing.
+/// It supports all string types which implement `From<&str>` and `AsRef<str>`, like `String`, `Box<str>`, or `Rc<str>`.
 #[derive(Copy, Clone, Debug, Default)]
 pub struct NoneAsEmptyString;
 
+/// De/Serialize an `Option<T>` using sentinel values for `None`
+///
+/// The [`Sentinel`] provides the values which are deserialized as `None`.
+/// They can be strings or integers.
+/// All other values are deserialized using the inner adapter `TAs` and wrapped in `Some`.
+/// Serialization emits the primary sentinel, i.e., the first value of [`Sentinel::sentinels`], for `None`.
+///
+/// Deserialization buffers the input to compare it against the sentinels, which requires a self-describing format.
+/// Predefined sentinels are [`sentinel::EmptyString`], [`sentinel::NotAvailable`], and [`sentinel::Zero`].
+///
+/// # Example
+///
+/// ```rust
+/// # use serde_derive::{Deserialize, Serialize};
+/// # use serde_with::{sentinel::NotAvailable, As, DisplayFromStr, NoneAs, Sentinel, SentinelValue};
+/// #
+/// struct Missing;
+///
+/// impl Sentinel for Missing {
+///     fn sentinels() -> &'static [SentinelValue] {
+///         &[
+///             SentinelValue::Str("N/A"),
+///             SentinelValue::Str("-"),
+///             SentinelValue::Str("null"),
+///             SentinelValue::Int(0),
+///         ]
+///     }
+/// }
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct Row {
+///     #[serde(with = "As::<NoneAs<NotAvailable, DisplayFromStr>>")]
+///     count: Option<u32>,
+///     #[serde(with = "As::<Vec<NoneAs<Missing>>>")]
+///     prices: Vec<Option<f64>>,
+/// }
+///
+/// let row: Row = serde_json::from_str(r#"{"count":"N/A","prices":[1.5,"-",0,"null"]}"#).unwrap();
+/// assert_eq!(None, row.count);
+/// assert_eq!(vec![Some(1.5), None, None, None], row.prices);
+/// assert_eq!(
+///     r#"{"count":"N/A","prices":[1.5,"N/A","N/A","N/A"]}"#,
+///     serde_json::to_string(&row).unwrap(),
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct NoneAs<SENTINEL: Sentinel, TAs = Same>(PhantomData<(SENTINEL, TAs)>);
+
 #[derive(Copy, Clone, Debug, Default)]
 pub struct DefaultOnError<T>(PhantomData<T>);
 
+/// Deserialize a `Vec<T>`, skipping elements which fail to deserialize
+///
+/// Every element is buffered and then deserialized using the adapter `TAs`.
+/// Elements which fail are dropped, so the input needs to be in a self-describing format.
+/// The [`SkipErrorHook`] is called with the error of every dropped element, e.g., to count or log them.
+/// [`IgnoreSkipped`] is the default and does nothing.
+/// Errors of the sequence itself, like syntax errors, still fail the deserialization.
+///
+/// Serialization works like `Vec<TAs>`.
+///
+/// [`DefaultOnError`] provides a similar behavior for single values.
+///
+/// # Example
+///
+/// ```rust
+/// # use serde_derive::{Deserialize, Serialize};
+/// # use serde_with::{As, Same, SkipErrorHook, VecSkipError};
+/// # use std::sync::atomic::{AtomicUsize, Ordering};
+/// #
+/// static SKIPPED: AtomicUsize = AtomicUsize::new(0);
+///
+/// struct CountSkipped;
+///
+/// impl SkipErrorHook for CountSkipped {
+///     fn skipped(_index: usize, _error: &dyn std::fmt::Display) {
+///         SKIPPED.fetch_add(1, Ordering::SeqCst);
+///     }
+/// }
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct Feed {
+///     #[serde(with = "As::<VecSkipError<Same>>")]
+///     ids: Vec<u32>,
+///     #[serde(with = "As::<VecSkipError<Same, CountSkipped>>")]
+///     scores: Vec<u8>,
+/// }
+///
+/// let feed: Feed = serde_json::from_str(r#"{"ids":[1,"two",3],"scores":[1,-1,300,2]}"#).unwrap();
+/// assert_eq!(vec![1, 3], feed.ids);
+/// assert_eq!(vec![1, 2], feed.scores);
+/// assert_eq!(2, SKIPPED.load(Ordering::SeqCst));
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct VecSkipError<TAs, HOOK: SkipErrorHook = IgnoreSkipped>(PhantomData<(TAs, HOOK)>);
+
+/// Deserialize a `BTreeMap` or `HashMap`, skipping entries which fail to deserialize
+///
+/// This is the counterpart of [`VecSkipError`] for maps.
+/// An entry is dropped if either the key or the value fail to deserialize using `KAs` or `VAs`.
+/// The [`SkipErrorHook`] is called with the error of every dropped entry.
+///
+/// Serialization works like `BTreeMap<KAs, VAs>` or `HashMap<KAs, VAs>`.
+///
+/// # Example
+///
+/// ```rust
+/// # use serde_derive::{Deserialize, Serialize};
+/// # use serde_with::{As, DisplayFromStr, MapSkipError, Same};
+/// # use std::collections::BTreeMap;
+/// #
+/// #[derive(Deserialize, Serialize)]
+/// struct Inventory {
+///     #[serde(with = "As::<MapSkipError<DisplayFromStr, Same>>")]
+///     stock: BTreeMap<u32, u32>,
+/// }
+///
+/// let inventory: Inventory =
+///     serde_json::from_str(r#"{"stock":{"1":5,"x":2,"3":"many","4":0}}"#).unwrap();
+/// assert_eq!(vec![(1, 5), (4, 0)], inventory.stock.into_iter().collect::<Vec<_>>());
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct MapSkipError<KAs, VAs, HOOK: SkipErrorHook = IgnoreSkipped>(
+    PhantomData<(KAs, VAs, HOOK)>,
+);
+
+/// Deserialize a map and return an error on duplicate keys
+///
+/// This is the [`serde_as`] version of [`rust::maps_duplicate_key_is_error`].
+/// The keys and values are deserialized using `KAs` and `VAs`.
+/// Serialization works like `BTreeMap<KAs, VAs>` or `HashMap<KAs, VAs>`.
+///
+/// Besides `BTreeMap` and `HashMap`, the adapter works for any map implementing [`PreventDuplicateInsertsMap`](duplicate_key_impls::PreventDuplicateInsertsMap) and [`MapEntry`](duplicate_key_impls::MapEntry).
+///
+/// [`serde_as`]: https://docs.rs/serde_with_macros/*/serde_with_macros/attr.serde_as.html
+///
+/// # Example
+///
+/// ```rust
+/// # use serde_derive::Deserialize;
+/// # use serde_with::{As, DisplayFromStr, MapPreventDuplicates, Same};
+/// # use std::collections::BTreeMap;
+/// #
+/// #[derive(Deserialize)]
+/// struct Doc {
+///     #[serde(with = "As::<Vec<MapPreventDuplicates<DisplayFromStr, Same>>>")]
+///     maps: Vec<BTreeMap<u32, u32>>,
+/// }
+///
+/// let doc: Doc = serde_json::from_str(r#"{"maps":[{"1":1},{"1":1,"2":2}]}"#).unwrap();
+/// assert_eq!(2, doc.maps.len());
+///
+/// let res: Result<Doc, _> = serde_json::from_str(r#"{"maps":[{"1":1},{"1":1,"1":2}]}"#);
+/// assert!(res.is_err());
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct MapPreventDuplicates<KAs, VAs>(PhantomData<(KAs, VAs)>);
+
+/// Deserialize a map and keep the value of the first occurrence of a key
+///
+/// This is the [`serde_as`] version of [`rust::maps_first_key_wins`].
+/// The keys and values are deserialized using `KAs` and `VAs`.
+/// Serialization works like `BTreeMap<KAs, VAs>` or `HashMap<KAs, VAs>`.
+///
+/// Besides `BTreeMap` and `HashMap`, the adapter works for any map implementing [`DuplicateInsertsFirstWinsMap`](duplicate_key_impls::DuplicateInsertsFirstWinsMap) and [`MapEntry`](duplicate_key_impls::MapEntry).
+///
+/// [`serde_as`]: https://docs.rs/serde_with_macros/*/serde_with_macros/attr.serde_as.html
+///
+/// # Example
+///
+/// ```rust
+/// # use serde_derive::Deserialize;
+/// # use serde_with::{As, MapFirstKeyWins, Same};
+/// # use std::collections::HashMap;
+/// #
+/// #[derive(Deserialize)]
+/// struct Doc {
+///     #[serde(with = "As::<MapFirstKeyWins<Same, Same>>")]
+///     map: HashMap<String, u32>,
+/// }
+///
+/// let doc: Doc = serde_json::from_str(r#"{"map":{"a":1,"b":2,"a":3}}"#).unwrap();
+/// assert_eq!(Some(&1), doc.map.get("a"));
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct MapFirstKeyWins<KAs, VAs>(PhantomData<(KAs, VAs)>);
+
+/// Deserialize a set and return an error on duplicate values
+///
+/// This is the [`serde_as`] version of [`rust::sets_duplicate_value_is_error`].
+/// The values are deserialized using `TAs`.
+/// Serialization works like `BTreeSet<TAs>` or `HashSet<TAs>`.
+///
+/// Besides `BTreeSet` and `HashSet`, the adapter works for any set implementing [`PreventDuplicateInsertsSet`](duplicate_key_impls::PreventDuplicateInsertsSet) and [`SetValue`](duplicate_key_impls::SetValue).
+///
+/// [`serde_as`]: https://docs.rs/serde_with_macros/*/serde_with_macros/attr.serde_as.html
+///
+/// # Example
+///
+/// ```rust
+/// # use serde_derive::Deserialize;
+/// # use serde_with::{As, DisplayFromStr, SetPreventDuplicates};
+/// # use std::collections::BTreeSet;
+/// #
+/// #[derive(Deserialize)]
+/// struct Doc {
+///     #[serde(with = "As::<SetPreventDuplicates<DisplayFromStr>>")]
+///     set: BTreeSet<u32>,
+/// }
+///
+/// let doc: Doc = serde_json::from_str(r#"{"set":["1","2","3"]}"#).unwrap();
+/// assert_eq!(3, doc.set.len());
+///
+/// let res: Result<Doc, _> = serde_json::from_str(r#"{"set":["1","2","1"]}"#);
+/// assert!(res.is_err());
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SetPreventDuplicates<TAs>(PhantomData<TAs>);
+
+/// Deserialize a set and keep the last of several equal values
+///
+/// Equal values are not necessarily identical, e.g., if the type only compares some of its fields.
+/// Inserting into a standard library set keeps the first value, this adapter replaces it instead.
+/// The values are deserialized using `TAs`.
+/// Serialization works like `BTreeSet<TAs>` or `HashSet<TAs>`.
+///
+/// Besides `BTreeSet` and `HashSet`, the adapter works for any set implementing [`DuplicateInsertsLastWinsSet`](duplicate_key_impls::DuplicateInsertsLastWinsSet) and [`SetValue`](duplicate_key_impls::SetValue).
+///
+/// # Example
+///
+/// ```rust
+/// # use serde_derive::Deserialize;
+/// # use serde_with::{As, Same, SetLastValueWins};
+/// # use std::{cmp::Ordering, collections::BTreeSet};
+/// #
+/// #[derive(Debug, Deserialize)]
+/// struct Entry(u32, String);
+/// #
+/// # impl PartialEq for Entry {
+/// #     fn eq(&self, other: &Self) -> bool {
+/// #         self.0 == other.0
+/// #     }
+/// # }
+/// # impl Eq for Entry {}
+/// # impl PartialOrd for Entry {
+/// #     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+/// #         Some(self.cmp(other))
+/// #     }
+/// # }
+/// # impl Ord for Entry {
+/// #     fn cmp(&self, other: &Self) -> Ordering {
+/// #         self.0.cmp(&other.0)
+/// #     }
+/// # }
+///
+/// // `Entry` only compares the number
+/// #[derive(Deserialize)]
+/// struct Doc {
+///     #[serde(with = "As::<SetLastValueWins<Same>>")]
+///     set: BTreeSet<Entry>,
+/// }
+///
+/// let doc: Doc = serde_json::from_str(r#"{"set":[[1,"first"],[1,"last"]]}"#).unwrap();
+/// assert_eq!("last", doc.set.iter().next().unwrap().1);
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SetLastValueWins<TAs>(PhantomData<TAs>);
+
 #[derive(Copy, Clone, Debug, Default)]
 pub struct BytesOrString;
 
+/// De/Serialize bytes efficiently, using `serialize_bytes`
+///
+/// Without this adapter, `Vec<u8>` and `[u8; N]` are serialized as a sequence of integers.
+/// Binary formats, like bincode or CBOR, can store bytes much more compactly.
+/// `Bytes` supports `Vec<u8>`, `[u8; N]` for `N` up to 32, `Box<[u8]>`, `&'de [u8]`, and `Cow<'de, [u8]>`.
+/// Serialization supports any type implementing `AsRef<[u8]>`.
+///
+/// Deserialization accepts bytes, strings, and sequences of integers.
+/// `&'de [u8]` and `Cow<'de, [u8]>` borrow from the input if the deserializer supports it.
+/// `&'de [u8]` fails if the data cannot be borrowed.
+///
+/// # Example
+///
+/// ```rust
+/// # use serde_derive::{Deserialize, Serialize};
+/// # use serde_with::{As, Bytes};
+/// # use std::borrow::Cow;
+/// #
+/// #[derive(Deserialize, Serialize)]
+/// struct Packet<'a> {
+///     #[serde(with = "As::<Bytes>")]
+///     header: [u8; 4],
+///     #[serde(borrow, with = "As::<Bytes>")]
+///     payload: Cow<'a, [u8]>,
+/// }
+///
+/// let packet: Packet<'_> = serde_json::from_str(r#"{"header":[1,2,3,4],"payload":"text"}"#).unwrap();
+/// assert_eq!([1, 2, 3, 4], packet.header);
+/// assert_eq!(Cow::Borrowed(b"text"), packet.payload);
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Bytes;
+
+/// De/Serialize a value by converting it into an intermediate type `T`
+///
+/// Serialization clones the value and converts it into `T` using [`Into`].
+/// Deserialization deserializes a `T` and converts it into the value using [`Into`].
+/// The adapter composes with the other adapters, e.g., `Vec<FromInto<u32>>` or `Option<FromInto<u32>>`.
+///
+/// Use [`TryFromInto`] if the conversions can fail.
+///
+/// # Example
+///
+/// ```rust
+/// # use serde_derive::{Deserialize, Serialize};
+/// # use serde_with::{As, FromInto};
+/// #
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Rgb {
+///     red: u8,
+///     green: u8,
+///     blue: u8,
+/// }
+///
+/// impl From<u32> for Rgb {
+///     fn from(value: u32) -> Self {
+///         Rgb {
+///             red: (value >> 16) as u8,
+///             green: (value >> 8) as u8,
+///             blue: value as u8,
+///         }
+///     }
+/// }
+///
+/// impl From<Rgb> for u32 {
+///     fn from(rgb: Rgb) -> Self {
+///         (u32::from(rgb.red) << 16) | (u32::from(rgb.green) << 8) | u32::from(rgb.blue)
+///     }
+/// }
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct Palette {
+///     #[serde(with = "As::<Vec<FromInto<u32>>>")]
+///     colors: Vec<Rgb>,
+/// }
+///
+/// let palette = Palette {
+///     colors: vec![Rgb { red: 255, green: 128, blue: 0 }],
+/// };
+/// assert_eq!(r#"{"colors":[16744448]}"#, serde_json::to_string(&palette).unwrap());
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct FromInto<T>(PhantomData<T>);
+
+/// De/Serialize a value by fallibly converting it into an intermediate type `T`
+///
+/// Serialization clones the value and converts it into `T` using [`TryInto`](std::convert::TryInto).
+/// Deserialization deserializes a `T` and converts it into the value using [`TryInto`](std::convert::TryInto).
+/// Conversion errors are turned into de/serialization errors, so they need to implement [`Display`](std::fmt::Display).
+///
+/// # Example
+///
+/// ```rust
+/// # use serde_derive::{Deserialize, Serialize};
+/// # use serde_with::{As, TryFromInto};
+/// #
+/// #[derive(Deserialize, Serialize)]
+/// struct Packet {
+///     #[serde(with = "As::<TryFromInto<u64>>")]
+///     length: u8,
+/// }
+///
+/// let packet: Packet = serde_json::from_str(r#"{"length":200}"#).unwrap();
+/// assert_eq!(200, packet.length);
+/// assert!(serde_json::from_str::<Packet>(r#"{"length":300}"#).is_err());
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TryFromInto<T>(PhantomData<T>);
+
+/// Deserialize a value using the first of several adapters which succeeds
+///
+/// The adapters are given as a tuple of two to four elements, e.g., `PickFirst<(Same, DisplayFromStr)>`.
+/// Deserialization buffers the input and then tries the adapters in order.
+/// If all of them fail, the error message lists the errors of every adapter.
+/// Buffering requires a self-describing format, i.e., one which supports `deserialize_any`.
+///
+/// Serialization always uses the first adapter.
+///
+/// # Example
+///
+/// ```rust
+/// # use serde_derive::{Deserialize, Serialize};
+/// # use serde_with::{As, DisplayFromStr, PickFirst, Same};
+/// #
+/// #[derive(Deserialize, Serialize)]
+/// struct Data {
+///     #[serde(with = "As::<PickFirst<(Same, DisplayFromStr)>>")]
+///     id: u32,
+///     #[serde(with = "As::<Vec<PickFirst<(Same, DisplayFromStr)>>>")]
+///     values: Vec<u32>,
+/// }
+///
+/// let data: Data = serde_json::from_str(r#"{"id":"123","values":[1,"2",3]}"#).unwrap();
+/// assert_eq!(123, data.id);
+/// assert_eq!(vec![1, 2, 3], data.values);
+/// assert_eq!(r#"{"id":123,"values":[1,2,3]}"#, serde_json::to_string(&data).unwrap());
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct PickFirst<T>(PhantomData<T>);
+
+/// De/Serialize a `Vec<T>` as a map, using the first field of every element as key
+///
+/// The elements need to be structs, tuples, or sequences.
+/// The first field of a struct must be renamed to `$key$`, e.g., using `#[serde(rename = "$key$")]`.
+/// It becomes the map key, while the remaining fields become the map value.
+/// For tuples and sequences the first element is the map key and the remaining elements are the map value.
+/// Deserialization reinserts the map key as first field, so `TAs` deserializes the complete element.
+///
+/// Serialization processes every element twice, once for the key and once for the value.
+/// Deserialization buffers the map keys and requires a self-describing format.
+///
+/// # Example
+///
+/// ```rust
+/// # use serde_derive::{Deserialize, Serialize};
+/// # use serde_with::{As, KeyValueMap, Same};
+/// #
+/// #[derive(Debug, PartialEq, Deserialize, Serialize)]
+/// struct User {
+///     #[serde(rename = "$key$")]
+///     name: String,
+///     age: u8,
+/// }
+///
+/// #[derive(Debug, PartialEq, Deserialize, Serialize)]
+/// struct Users {
+///     #[serde(with = "As::<KeyValueMap<Same>>")]
+///     users: Vec<User>,
+/// }
+///
+/// let users = Users {
+///     users: vec![
+///         User { name: "alice".into(), age: 3 },
+///         User { name: "bob".into(), age: 5 },
+///     ],
+/// };
+/// let json = r#"{"users":{"alice":{"age":3},"bob":{"age":5}}}"#;
+/// assert_eq!(json, serde_json::to_string(&users).unwrap());
+/// assert_eq!(users, serde_json::from_str(json).unwrap());
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct KeyValueMap<TAs>(PhantomData<TAs>);
+
+/// Deserialize a `Vec<T>` from a single value or from a sequence
+///
+/// A single value is deserialized as a vector with one element.
+/// The adapter `TAs` is applied to every element.
+/// Deserialization buffers the input, which requires a self-describing format.
+/// It first tries to deserialize a single element and then a sequence, so a `T` which is a sequence itself takes precedence.
+///
+/// The [`Preference`] decides how vectors with exactly one element are serialized.
+/// [`PreferOne`] emits the bare element, [`PreferMany`] always emits a sequence.
+/// All other lengths are serialized as sequences.
+///
+/// # Example
+///
+/// ```rust
+/// # use serde_derive::{Deserialize, Serialize};
+/// # use serde_with::{As, DisplayFromStr, OneOrMany, PreferMany, Same};
+/// #
+/// #[derive(Deserialize, Serialize)]
+/// struct Activity {
+///     #[serde(with = "As::<OneOrMany>")]
+///     to: Vec<String>,
+///     #[serde(with = "As::<OneOrMany<DisplayFromStr, PreferMany>>")]
+///     ids: Vec<u32>,
+/// }
+///
+/// let activity: Activity = serde_json::from_str(r#"{"to":"alice","ids":"1"}"#).unwrap();
+/// assert_eq!(vec!["alice"], activity.to);
+/// assert_eq!(vec![1], activity.ids);
+/// assert_eq!(r#"{"to":"alice","ids":["1"]}"#, serde_json::to_string(&activity).unwrap());
+///
+/// let activity: Activity = serde_json::from_str(r#"{"to":["alice","bob"],"ids":["1","2"]}"#).unwrap();
+/// assert_eq!(vec!["alice", "bob"], activity.to);
+/// assert_eq!(vec![1, 2], activity.ids);
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct OneOrMany<TAs = Same, PREFERENCE: Preference = PreferOne>(
+    PhantomData<(TAs, PREFERENCE)>,
+);
+
 pub trait Format {}
 pub trait Strictness {}
 
@@ -199,6 +842,48 @@ impl Strictness for Strict {}
 pub struct Flexible;
 impl Strictness for Flexible {}
 
+/// Padding behavior of the byte encodings, like `Base64` or `Base32`
+///
+/// The types are re-exported from the `base64` and `base32` modules.
+pub trait Padding {
+    /// Write `=` padding characters while serializing
+    const PAD: bool;
+}
+
+/// Pad the output with `=` to a multiple of the block size of the encoding
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Padded;
+
+impl Padding for Padded {
+    const PAD: bool = true;
+}
+
+/// Omit the `=` padding characters
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Unpadded;
+
+impl Padding for Unpadded {
+    const PAD: bool = false;
+}
+
+/// Serialization behavior of [`OneOrMany`] for a single element
+pub trait Preference {
+    /// Serialize a single element without the surrounding sequence
+    const PREFER_ONE: bool;
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct PreferOne;
+impl Preference for PreferOne {
+    const PREFER_ONE: bool = true;
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct PreferMany;
+impl Preference for PreferMany {
+    const PREFER_ONE: bool = false;
+}
+
 #[derive(Copy, Clone, Debug, Default)]
 pub struct DurationSeconds<FORMAT: Format = Integer, STRICTNESS: Strictness = Strict>(
     PhantomData<(FORMAT, STRICTNESS)>,
@@ -208,3 +893,113 @@ pub struct DurationSeconds<FORMAT: Format = Integer, STRICTNESS: Strictness = St
 pub struct DurationSecondsWithFrac<FORMAT: Format = f64, STRICTNESS: Strictness = Strict>(
     PhantomData<(FORMAT, STRICTNESS)>,
 );
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DurationMilliSeconds<FORMAT: Format = Integer, STRICTNESS: Strictness = Strict>(
+    PhantomData<(FORMAT, STRICTNESS)>,
+);
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DurationMilliSecondsWithFrac<FORMAT: Format = f64, STRICTNESS: Strictness = Strict>(
+    PhantomData<(FORMAT, STRICTNESS)>,
+);
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DurationMicroSeconds<FORMAT: Format = Integer, STRICTNESS: Strictness = Strict>(
+    PhantomData<(FORMAT, STRICTNESS)>,
+);
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DurationMicroSecondsWithFrac<FORMAT: Format = f64, STRICTNESS: Strictness = Strict>(
+    PhantomData<(FORMAT, STRICTNESS)>,
+);
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DurationNanoSeconds<FORMAT: Format = Integer, STRICTNESS: Strictness = Strict>(
+    PhantomData<(FORMAT, STRICTNESS)>,
+);
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DurationNanoSecondsWithFrac<FORMAT: Format = f64, STRICTNESS: Strictness = Strict>(
+    PhantomData<(FORMAT, STRICTNESS)>,
+);
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TimestampSeconds<FORMAT: Format = Integer, STRICTNESS: Strictness = Strict>(
+    PhantomData<(FORMAT, STRICTNESS)>,
+);
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TimestampSecondsWithFrac<FORMAT: Format = f64, STRICTNESS: Strictness = Strict>(
+    PhantomData<(FORMAT, STRICTNESS)>,
+);
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TimestampMilliSeconds<FORMAT: Format = Integer, STRICTNESS: Strictness = Strict>(
+    PhantomData<(FORMAT, STRICTNESS)>,
+);
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TimestampMilliSecondsWithFrac<FORMAT: Format = f64, STRICTNESS: Strictness = Strict>(
+    PhantomData<(FORMAT, STRICTNESS)>,
+);
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TimestampMicroSeconds<FORMAT: Format = Integer, STRICTNESS: Strictness = Strict>(
+    PhantomData<(FORMAT, STRICTNESS)>,
+);
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TimestampMicroSecondsWithFrac<FORMAT: Format = f64, STRICTNESS: Strictness = Strict>(
+    PhantomData<(FORMAT, STRICTNESS)>,
+);
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TimestampNanoSeconds<FORMAT: Format = Integer, STRICTNESS: Strictness = Strict>(
+    PhantomData<(FORMAT, STRICTNESS)>,
+);
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TimestampNanoSecondsWithFrac<FORMAT: Format = f64, STRICTNESS: Strictness = Strict>(
+    PhantomData<(FORMAT, STRICTNESS)>,
+);
+
+/// Designators which [`Iso8601Duration`] accepts while deserializing
+pub trait Iso8601Designators {
+    /// Accept the day and week designators, like `P3DT4H`
+    const DAYS_AND_WEEKS: bool;
+}
+
+/// Only accept the hours, minutes, and seconds designators, like `PT1H30M5.25S`
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TimeDesignators;
+impl Iso8601Designators for TimeDesignators {
+    const DAYS_AND_WEEKS: bool = false;
+}
+
+/// Also accept the day and week designators, like `P3DT4H`, and treat a day as 24 hours
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DayDesignators;
+impl Iso8601Designators for DayDesignators {
+    const DAYS_AND_WEEKS: bool = true;
+}
+
+/// De/Serialize durations as ISO 8601 strings, like `PT1H30M5.25S`
+///
+/// Serialization always uses the hours, minutes, and seconds components.
+/// Deserialization with [`TimeDesignators`] only accepts these time components.
+/// [`DayDesignators`] also accepts the day and week designators.
+/// Years and months are never accepted, since their length depends on the calendar.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Iso8601Duration<DESIGNATORS: Iso8601Designators = TimeDesignators>(
+    PhantomData<DESIGNATORS>,
+);
+
+/// De/Serialize durations as human-readable strings, like `30s`, `1h 15m`, or `250ms`
+///
+/// The supported units are `ns`, `us`, `ms`, `s`, `m`, `h`, and `d`.
+/// Serialization uses the shortest exact representation, which only has a fraction for the units of seconds and below, like `1.5s`.
+/// Deserialization accepts a fraction for every unit, like `1.5h` or `0.5d`.
+/// [`Flexible`] also accepts plain numbers of seconds, like [`DurationSeconds`] does.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DurationHumanString<STRICTNESS: Strictness = Strict>(PhantomData<STRICTNESS>);
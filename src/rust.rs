@@ -6,6 +6,7 @@ use serde::{
     ser::{Serialize, SerializeMap, SerializeSeq, Serializer},
 };
 use std::{
+    borrow::Cow,
     cmp::Eq,
     collections::{BTreeMap, HashMap},
     fmt::{self, Display},
@@ -86,7 +87,7 @@ pub mod display_fromstr {
     /// Serialize T using [Display]
     pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
     where
-        T: Display,
+        T: Display + ?Sized,
         S: Serializer,
     {
         serializer.serialize_str(&*value.to_string())
@@ -634,6 +635,91 @@ pub mod maps_duplicate_key_is_error {
     }
 }
 
+/// Ensure no duplicate keys exist in a map and report all of them at once.
+///
+/// This works like [`maps_duplicate_key_is_error`], but the map is read completely before returning an error.
+/// The error lists every duplicate key together with the values of all its occurrences,
+/// using their [`Debug`](std::fmt::Debug) representation.
+/// This allows fixing all problems of the serialized data in one pass.
+///
+/// The error is built from a [`DuplicateKeys`] value, which is converted into a string by [`Error::custom`].
+/// Hand-written visitors can keep the structured [`DuplicateKeys`] by using [`collect_duplicate_keys`] instead.
+///
+/// The implementation supports both the [`HashMap`] and the [`BTreeMap`] from the standard library.
+/// Other maps can be supported by implementing [`CollectDuplicateInsertsMap`].
+///
+/// [`DuplicateKeys`]: crate::duplicate_key_impls::DuplicateKeys
+/// [`collect_duplicate_keys`]: crate::duplicate_key_impls::collect_duplicate_keys
+/// [`CollectDuplicateInsertsMap`]: crate::duplicate_key_impls::CollectDuplicateInsertsMap
+///
+/// # Example
+///
+/// ```rust
+/// # use serde_derive::Deserialize;
+/// # use std::collections::BTreeMap;
+/// #
+/// # #[derive(Debug, Eq, PartialEq)]
+/// #[derive(Deserialize)]
+/// struct Doc {
+///     #[serde(with = "::serde_with::rust::maps_report_all_duplicate_keys")]
+///     map: BTreeMap<String, usize>,
+/// }
+///
+/// let s = r#"{"map": {"a": 1, "b": 2, "a": 3, "b": 4, "a": 5}}"#;
+/// let res: Result<Doc, _> = serde_json::from_str(s);
+/// assert_eq!(
+///     r#"invalid entries: found 2 duplicate keys: "a" with 3 values: 1, 3, 5; "b" with 2 values: 2, 4 at line 1 column 48"#,
+///     res.unwrap_err().to_string(),
+/// );
+/// ```
+pub mod maps_report_all_duplicate_keys {
+    use super::*;
+    use crate::duplicate_key_impls::{collect_duplicate_keys, CollectDuplicateInsertsMap};
+
+    /// Deserialize a map and return an error listing all duplicate keys
+    pub fn deserialize<'de, D, T, K, V>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: CollectDuplicateInsertsMap<K, V>,
+        K: Deserialize<'de> + fmt::Debug,
+        V: Deserialize<'de> + fmt::Debug,
+        D: Deserializer<'de>,
+    {
+        struct MapVisitor<T, K, V> {
+            marker: PhantomData<T>,
+            map_key_type: PhantomData<K>,
+            map_value_type: PhantomData<V>,
+        };
+
+        impl<'de, T, K, V> Visitor<'de> for MapVisitor<T, K, V>
+        where
+            T: CollectDuplicateInsertsMap<K, V>,
+            K: Deserialize<'de> + fmt::Debug,
+            V: Deserialize<'de> + fmt::Debug,
+        {
+            type Value = T;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            #[inline]
+            fn visit_map<A>(self, access: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                collect_duplicate_keys(access)?.map_err(Error::custom)
+            }
+        }
+
+        let visitor = MapVisitor {
+            marker: PhantomData,
+            map_key_type: PhantomData,
+            map_value_type: PhantomData,
+        };
+        deserializer.deserialize_map(visitor)
+    }
+}
+
 /// Ensure that the first value is taken, if duplicate values exist
 ///
 /// By default serde has a last-value-wins implementation, if duplicate keys for a set exist.
@@ -1306,32 +1392,45 @@ pub mod bytes_or_string {
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_any(BytesOrStringVisitor)
+        deserializer
+            .deserialize_any(BytesOrStringVisitor)
+            .map(Cow::into_owned)
     }
 
-    struct BytesOrStringVisitor;
+    /// Visitor accepting bytes, strings, and sequences of bytes
+    ///
+    /// Borrowed bytes and strings are returned without copying them.
+    pub(crate) struct BytesOrStringVisitor;
 
     impl<'de> Visitor<'de> for BytesOrStringVisitor {
-        type Value = Vec<u8>;
+        type Value = Cow<'de, [u8]>;
 
         fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
             formatter.write_str("a list of bytes or a string")
         }
 
+        fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+            Ok(Cow::Borrowed(v))
+        }
+
         fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E> {
-            Ok(v.to_vec())
+            Ok(Cow::Owned(v.to_vec()))
         }
 
         fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E> {
-            Ok(v)
+            Ok(Cow::Owned(v))
+        }
+
+        fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E> {
+            Ok(Cow::Borrowed(v.as_bytes()))
         }
 
         fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
-            Ok(v.as_bytes().to_vec())
+            Ok(Cow::Owned(v.as_bytes().to_vec()))
         }
 
         fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
-            Ok(v.into_bytes())
+            Ok(Cow::Owned(v.into_bytes()))
         }
 
         fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
@@ -1342,7 +1441,7 @@ pub mod bytes_or_string {
             while let Some(value) = seq.next_element()? {
                 res.push(value);
             }
-            Ok(res)
+            Ok(Cow::Owned(res))
         }
     }
 }
@@ -0,0 +1,38 @@
+//! Predefined sentinels for [`NoneAs`](crate::NoneAs)
+//!
+//! Custom sentinels implement the [`Sentinel`] trait.
+
+use crate::{Sentinel, SentinelValue};
+
+/// Predefined sentinel using the empty string
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct EmptyString;
+
+impl Sentinel for EmptyString {
+    #[inline]
+    fn sentinels() -> &'static [SentinelValue] {
+        &[SentinelValue::Str("")]
+    }
+}
+
+/// Predefined sentinel using the string `"N/A"`
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct NotAvailable;
+
+impl Sentinel for NotAvailable {
+    #[inline]
+    fn sentinels() -> &'static [SentinelValue] {
+        &[SentinelValue::Str("N/A")]
+    }
+}
+
+/// Predefined sentinel using the integer `0`
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Zero;
+
+impl Sentinel for Zero {
+    #[inline]
+    fn sentinels() -> &'static [SentinelValue] {
+        &[SentinelValue::Int(0)]
+    }
+}
@@ -1,10 +1,25 @@
 use super::*;
+use crate::{
+    duplicate_key_impls::{MapEntry, SetValue},
+    key_value_map,
+    utils::{
+        self,
+        duration::{format_human_duration, format_iso8601_duration, DurationSigned},
+        MICROS_PER_SEC, MILLIS_PER_SEC, NANOS_PER_SEC,
+    },
+};
+use serde::ser::{Error, SerializeMap};
 use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
     collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque},
+    convert::TryInto,
     fmt::Display,
     hash::{BuildHasher, Hash},
     marker::PhantomData,
-    time::Duration,
+    rc::Rc,
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, SystemTime},
 };
 
 impl<T: Serialize> SerializeAs<T> for SameAs<T> {
@@ -31,12 +46,12 @@ where
     }
 }
 
-pub(crate) struct SerializeAsWrap<'a, T, U> {
+pub(crate) struct SerializeAsWrap<'a, T: ?Sized, U: ?Sized> {
     value: &'a T,
     marker: PhantomData<U>,
 }
 
-impl<'a, T, U> SerializeAsWrap<'a, T, U> {
+impl<'a, T: ?Sized, U: ?Sized> SerializeAsWrap<'a, T, U> {
     pub(crate) fn new(value: &'a T) -> Self {
         Self {
             value,
@@ -45,7 +60,7 @@ impl<'a, T, U> SerializeAsWrap<'a, T, U> {
     }
 }
 
-impl<'a, T, U> Serialize for SerializeAsWrap<'a, T, U>
+impl<'a, T: ?Sized, U: ?Sized> Serialize for SerializeAsWrap<'a, T, U>
 where
     U: SerializeAs<T>,
 {
@@ -57,6 +72,111 @@ where
     }
 }
 
+macro_rules! pointer_impl {
+    ($($ty:ident $(,)?)+) => {$(
+        impl<T, U> SerializeAs<$ty<T>> for $ty<U>
+        where
+            T: ?Sized,
+            U: SerializeAs<T> + ?Sized,
+        {
+            fn serialize_as<S>(source: &$ty<T>, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                SerializeAsWrap::<T, U>::new(&**source).serialize(serializer)
+            }
+        }
+    )+};
+}
+
+pointer_impl!(Box, Rc, Arc);
+
+impl<'a, T, U> SerializeAs<Cow<'a, T>> for Cow<'a, U>
+where
+    T: ToOwned + ?Sized,
+    U: SerializeAs<T> + ToOwned + ?Sized,
+{
+    fn serialize_as<S>(source: &Cow<'a, T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        SerializeAsWrap::<T, U>::new(&**source).serialize(serializer)
+    }
+}
+
+impl<T, U> SerializeAs<Cell<T>> for Cell<U>
+where
+    T: Copy,
+    U: SerializeAs<T>,
+{
+    fn serialize_as<S>(source: &Cell<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        SerializeAsWrap::<T, U>::new(&source.get()).serialize(serializer)
+    }
+}
+
+impl<T, U> SerializeAs<RefCell<T>> for RefCell<U>
+where
+    T: ?Sized,
+    U: SerializeAs<T> + ?Sized,
+{
+    fn serialize_as<S>(source: &RefCell<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match source.try_borrow() {
+            Ok(value) => SerializeAsWrap::<T, U>::new(&*value).serialize(serializer),
+            Err(_) => Err(S::Error::custom("already mutably borrowed")),
+        }
+    }
+}
+
+impl<T, U> SerializeAs<Mutex<T>> for Mutex<U>
+where
+    T: ?Sized,
+    U: SerializeAs<T> + ?Sized,
+{
+    fn serialize_as<S>(source: &Mutex<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match source.lock() {
+            Ok(value) => SerializeAsWrap::<T, U>::new(&*value).serialize(serializer),
+            Err(_) => Err(S::Error::custom("lock poison error while serializing")),
+        }
+    }
+}
+
+impl<T, U> SerializeAs<RwLock<T>> for RwLock<U>
+where
+    T: ?Sized,
+    U: SerializeAs<T> + ?Sized,
+{
+    fn serialize_as<S>(source: &RwLock<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match source.read() {
+            Ok(value) => SerializeAsWrap::<T, U>::new(&*value).serialize(serializer),
+            Err(_) => Err(S::Error::custom("lock poison error while serializing")),
+        }
+    }
+}
+
+impl<T, U> SerializeAs<[T]> for [U]
+where
+    U: SerializeAs<T>,
+{
+    fn serialize_as<S>(source: &[T], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(source.iter().map(|item| SerializeAsWrap::<T, U>::new(item)))
+    }
+}
+
 macro_rules! seq_impl {
     ($ty:ident < T $(: $tbound1:ident $(+ $tbound2:ident)*)* $(, $typaram:ident : $bound:ident)* >) => {
         impl<T, U $(, $typaram)*> SerializeAs<$ty<T $(, $typaram)*>> for $ty<U $(, $typaram)*>
@@ -104,9 +224,124 @@ macro_rules! map_impl {
 map_impl!(BTreeMap<K: Ord, V>);
 map_impl!(HashMap<K: Eq + Hash, V, H: BuildHasher>);
 
+impl<K, V, VAs> SerializeAs<BTreeMap<K, V>> for MapKeysDisplayFromStr<VAs>
+where
+    K: Display + Ord,
+    VAs: SerializeAs<V>,
+{
+    fn serialize_as<S>(source: &BTreeMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        BTreeMap::<DisplayFromStr, VAs>::serialize_as(source, serializer)
+    }
+}
+
+impl<K, V, VAs, H> SerializeAs<HashMap<K, V, H>> for MapKeysDisplayFromStr<VAs>
+where
+    K: Display + Eq + Hash,
+    VAs: SerializeAs<V>,
+    H: BuildHasher,
+{
+    fn serialize_as<S>(source: &HashMap<K, V, H>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        HashMap::<DisplayFromStr, VAs, H>::serialize_as(source, serializer)
+    }
+}
+
+impl<T, TAs, HOOK> SerializeAs<Vec<T>> for VecSkipError<TAs, HOOK>
+where
+    TAs: SerializeAs<T>,
+    HOOK: SkipErrorHook,
+{
+    fn serialize_as<S>(source: &Vec<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Vec::<TAs>::serialize_as(source, serializer)
+    }
+}
+
+macro_rules! map_skip_error_impl {
+    ($ty:ident < K $(: $kbound1:ident $(+ $kbound2:ident)*)*, V $(, $typaram:ident : $bound:ident)* >) => {
+        impl<K, KAs, V, VAs, HOOK $(, $typaram)*> SerializeAs<$ty<K, V $(, $typaram)*>> for MapSkipError<KAs, VAs, HOOK>
+        where
+            KAs: SerializeAs<K>,
+            VAs: SerializeAs<V>,
+            HOOK: SkipErrorHook,
+            $(K: $kbound1 $(+ $kbound2)*,)*
+            $($typaram: $bound,)*
+        {
+            fn serialize_as<S>(source: &$ty<K, V $(, $typaram)*>, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                $ty::<KAs, VAs $(, $typaram)*>::serialize_as(source, serializer)
+            }
+        }
+    }
+}
+
+map_skip_error_impl!(BTreeMap<K: Ord, V>);
+map_skip_error_impl!(HashMap<K: Eq + Hash, V, H: BuildHasher>);
+
+macro_rules! set_duplicate_policy_impl {
+    ($adapter:ident) => {
+        impl<SET, TAs> SerializeAs<SET> for $adapter<TAs>
+        where
+            SET: SetValue,
+            for<'a> &'a SET: IntoIterator<Item = &'a SET::Value>,
+            TAs: SerializeAs<SET::Value>,
+        {
+            fn serialize_as<S>(source: &SET, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.collect_seq(
+                    source
+                        .into_iter()
+                        .map(|value| SerializeAsWrap::<SET::Value, TAs>::new(value)),
+                )
+            }
+        }
+    };
+}
+
+set_duplicate_policy_impl!(SetPreventDuplicates);
+set_duplicate_policy_impl!(SetLastValueWins);
+
+macro_rules! map_duplicate_policy_impl {
+    ($adapter:ident) => {
+        impl<MAP, KAs, VAs> SerializeAs<MAP> for $adapter<KAs, VAs>
+        where
+            MAP: MapEntry,
+            for<'a> &'a MAP: IntoIterator<Item = (&'a MAP::Key, &'a MAP::Value)>,
+            KAs: SerializeAs<MAP::Key>,
+            VAs: SerializeAs<MAP::Value>,
+        {
+            fn serialize_as<S>(source: &MAP, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.collect_map(source.into_iter().map(|(key, value)| {
+                    (
+                        SerializeAsWrap::<MAP::Key, KAs>::new(key),
+                        SerializeAsWrap::<MAP::Value, VAs>::new(value),
+                    )
+                }))
+            }
+        }
+    };
+}
+
+map_duplicate_policy_impl!(MapPreventDuplicates);
+map_duplicate_policy_impl!(MapFirstKeyWins);
+
 impl<T> SerializeAs<T> for DisplayFromStr
 where
-    T: Display,
+    T: Display + ?Sized,
 {
     fn serialize_as<S>(source: &T, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -219,7 +454,7 @@ array_impl!(30);
 array_impl!(31);
 array_impl!(32);
 
-impl<T: Serialize> SerializeAs<T> for Same {
+impl<T: Serialize + ?Sized> SerializeAs<T> for Same {
     fn serialize_as<S>(source: &T, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
@@ -252,6 +487,18 @@ macro_rules! map_as_tuple_seq {
 map_as_tuple_seq!(BTreeMap<K: Ord, V>);
 map_as_tuple_seq!(HashMap<K: Eq + Hash, V, H: BuildHasher>);
 
+impl<AsRefStr> SerializeAs<AsRefStr> for utils::StrLike
+where
+    AsRefStr: AsRef<str>,
+{
+    fn serialize_as<S>(source: &AsRefStr, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(source.as_ref())
+    }
+}
+
 impl<AsRefStr> SerializeAs<Option<AsRefStr>> for NoneAsEmptyString
 where
     AsRefStr: AsRef<str>,
@@ -260,7 +507,27 @@ where
     where
         S: Serializer,
     {
-        crate::rust::string_empty_as_none::serialize(source, serializer)
+        NoneAs::<sentinel::EmptyString, utils::StrLike>::serialize_as(source, serializer)
+    }
+}
+
+impl<SENTINEL, T, TAs> SerializeAs<Option<T>> for NoneAs<SENTINEL, TAs>
+where
+    SENTINEL: Sentinel,
+    TAs: SerializeAs<T>,
+{
+    fn serialize_as<S>(source: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *source {
+            Some(ref value) => TAs::serialize_as(value, serializer),
+            None => match SENTINEL::sentinels().first() {
+                Some(SentinelValue::Str(value)) => serializer.serialize_str(value),
+                Some(SentinelValue::Int(value)) => serializer.serialize_i64(*value),
+                None => Err(S::Error::custom("the sentinel does not provide any value")),
+            },
+        }
     }
 }
 
@@ -331,67 +598,126 @@ impl SerializeAs<Vec<u8>> for BytesOrString {
     }
 }
 
-impl<STRICTNESS> SerializeAs<Duration> for DurationSeconds<Integer, STRICTNESS>
+impl<T> SerializeAs<T> for Bytes
 where
-    STRICTNESS: Strictness,
+    T: AsRef<[u8]> + ?Sized,
 {
-    fn serialize_as<S>(source: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    fn serialize_as<S>(source: &T, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let mut secs = source.as_secs();
-        // Properly round the value
-        if source.subsec_millis() >= 500 {
-            secs += 1;
-        }
-        secs.serialize(serializer)
+        serializer.serialize_bytes(source.as_ref())
     }
 }
 
-impl<STRICTNESS> SerializeAs<Duration> for DurationSeconds<f64, STRICTNESS>
+impl<T, U> SerializeAs<T> for FromInto<U>
 where
-    STRICTNESS: Strictness,
+    T: Into<U> + Clone,
+    U: Serialize,
 {
-    fn serialize_as<S>(source: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    fn serialize_as<S>(source: &T, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        utils::duration_as_secs_f64(source)
-            .round()
+        source.clone().into().serialize(serializer)
+    }
+}
+
+impl<T, U> SerializeAs<T> for TryFromInto<U>
+where
+    T: TryInto<U> + Clone,
+    <T as TryInto<U>>::Error: Display,
+    U: Serialize,
+{
+    fn serialize_as<S>(source: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        source
+            .clone()
+            .try_into()
+            .map_err(S::Error::custom)?
             .serialize(serializer)
     }
 }
 
-impl<STRICTNESS> SerializeAs<Duration> for DurationSeconds<String, STRICTNESS>
+macro_rules! pick_first_impl {
+    ($len:literal 0 $first:ident $($n:tt $tas:ident)+) => {
+        impl<T, $first, $($tas,)+> SerializeAs<T> for PickFirst<($first, $($tas,)+)>
+        where
+            T: ?Sized,
+            $first: SerializeAs<T>,
+        {
+            fn serialize_as<S>(source: &T, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                $first::serialize_as(source, serializer)
+            }
+        }
+    };
+}
+
+pick_first_impl!(2 0 As0 1 As1);
+pick_first_impl!(3 0 As0 1 As1 2 As2);
+pick_first_impl!(4 0 As0 1 As1 2 As2 3 As3);
+
+impl<T, TAs> SerializeAs<Vec<T>> for KeyValueMap<TAs>
+where
+    TAs: SerializeAs<T>,
+{
+    fn serialize_as<S>(source: &Vec<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(source.len()))?;
+        for element in source {
+            let element = SerializeAsWrap::<T, TAs>::new(element);
+            key_value_map::serialize_key(&mut map, &element)?;
+            map.serialize_value(&key_value_map::WithoutKey(&element))?;
+        }
+        map.end()
+    }
+}
+
+impl<T, TAs, PREFERENCE> SerializeAs<Vec<T>> for OneOrMany<TAs, PREFERENCE>
 where
-    STRICTNESS: Strictness,
+    TAs: SerializeAs<T>,
+    PREFERENCE: Preference,
 {
-    fn serialize_as<S>(source: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    fn serialize_as<S>(source: &Vec<T>, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let mut secs = source.as_secs();
-        // Properly round the value
-        if source.subsec_millis() >= 500 {
-            secs += 1;
+        match source.len() {
+            1 if PREFERENCE::PREFER_ONE => TAs::serialize_as(&source[0], serializer),
+            _ => Vec::<TAs>::serialize_as(source, serializer),
         }
-        secs.to_string().serialize(serializer)
     }
 }
 
-impl<STRICTNESS> SerializeAs<Duration> for DurationSecondsWithFrac<f64, STRICTNESS>
+use_duration_signed_ser!(Duration => DurationSeconds, DurationSecondsWithFrac, 1);
+use_duration_signed_ser!(Duration => DurationMilliSeconds, DurationMilliSecondsWithFrac, MILLIS_PER_SEC);
+use_duration_signed_ser!(Duration => DurationMicroSeconds, DurationMicroSecondsWithFrac, MICROS_PER_SEC);
+use_duration_signed_ser!(Duration => DurationNanoSeconds, DurationNanoSecondsWithFrac, NANOS_PER_SEC);
+use_duration_signed_ser!(SystemTime => TimestampSeconds, TimestampSecondsWithFrac, 1);
+use_duration_signed_ser!(SystemTime => TimestampMilliSeconds, TimestampMilliSecondsWithFrac, MILLIS_PER_SEC);
+use_duration_signed_ser!(SystemTime => TimestampMicroSeconds, TimestampMicroSecondsWithFrac, MICROS_PER_SEC);
+use_duration_signed_ser!(SystemTime => TimestampNanoSeconds, TimestampNanoSecondsWithFrac, NANOS_PER_SEC);
+
+impl<DESIGNATORS> SerializeAs<Duration> for Iso8601Duration<DESIGNATORS>
 where
-    STRICTNESS: Strictness,
+    DESIGNATORS: Iso8601Designators,
 {
     fn serialize_as<S>(source: &Duration, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        utils::duration_as_secs_f64(source).serialize(serializer)
+        format_iso8601_duration(DurationSigned::from(source)).serialize(serializer)
     }
 }
 
-impl<STRICTNESS> SerializeAs<Duration> for DurationSecondsWithFrac<String, STRICTNESS>
+impl<STRICTNESS> SerializeAs<Duration> for DurationHumanString<STRICTNESS>
 where
     STRICTNESS: Strictness,
 {
@@ -399,8 +725,6 @@ where
     where
         S: Serializer,
     {
-        utils::duration_as_secs_f64(source)
-            .to_string()
-            .serialize(serializer)
+        format_human_duration(DurationSigned::from(source)).serialize(serializer)
     }
 }
@@ -2,7 +2,7 @@ use super::*;
 
 mod impls;
 
-pub trait SerializeAs<T> {
+pub trait SerializeAs<T: ?Sized> {
     fn serialize_as<S>(source: &T, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer;
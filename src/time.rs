@@ -0,0 +1,201 @@
+//! De/Serialization of [time][] types
+//!
+//! This modules is only available if using the `time` feature of the crate.
+//!
+//! The `TimestampSeconds` family of adapters supports `OffsetDateTime` and `PrimitiveDateTime`.
+//! The `DurationSeconds` family of adapters, `Iso8601Duration`, and `DurationHumanString` support the signed `time::Duration`.
+//!
+//! [time]: https://docs.rs/time/
+
+use crate::{
+    de::DeserializeAs,
+    ser::SerializeAs,
+    utils::{
+        duration::{
+            deserialize_iso8601_duration, format_human_duration, format_iso8601_duration,
+            DurationHumanStringVisitor, DurationSigned, Sign,
+        },
+        MICROS_PER_SEC, MILLIS_PER_SEC, NANOS_PER_SEC,
+    },
+    DurationHumanString, Flexible, Iso8601Designators, Iso8601Duration, Strict, Strictness,
+};
+use serde::{de, Deserializer, Serialize, Serializer};
+use std::convert::TryFrom;
+use time_crate::{Date, Duration, OffsetDateTime, PrimitiveDateTime};
+
+/// Create a [`PrimitiveDateTime`] for the Unix Epoch
+fn unix_epoch_primitive() -> PrimitiveDateTime {
+    let epoch = OffsetDateTime::unix_epoch();
+    PrimitiveDateTime::new(epoch.date(), epoch.time())
+}
+
+/// Smallest and largest [`PrimitiveDateTime`] supported by the time crate
+fn datetime_bounds() -> (PrimitiveDateTime, PrimitiveDateTime) {
+    let min = Date::try_from_ymd(-100_000, 1, 1)
+        .expect("The year is in the supported range")
+        .midnight();
+    let max = Date::try_from_ymd(100_000, 12, 31)
+        .and_then(|date| date.try_with_hms_nano(23, 59, 59, 999_999_999))
+        .expect("The year is in the supported range");
+    (min, max)
+}
+
+impl From<&Duration> for DurationSigned {
+    fn from(dur: &Duration) -> Self {
+        // Seconds and nanoseconds always share the sign of the whole duration.
+        let secs = dur.whole_seconds();
+        let nanos = dur.subsec_nanoseconds();
+        if dur.is_negative() {
+            DurationSigned::new(
+                Sign::Negative,
+                secs.wrapping_neg() as u64,
+                nanos.wrapping_neg() as u32,
+            )
+        } else {
+            DurationSigned::new(Sign::Positive, secs as u64, nanos as u32)
+        }
+    }
+}
+
+impl From<&OffsetDateTime> for DurationSigned {
+    fn from(datetime: &OffsetDateTime) -> Self {
+        Self::from(&(*datetime - OffsetDateTime::unix_epoch()))
+    }
+}
+
+impl From<&PrimitiveDateTime> for DurationSigned {
+    fn from(datetime: &PrimitiveDateTime) -> Self {
+        Self::from(&(*datetime - unix_epoch_primitive()))
+    }
+}
+
+fn duration_into_time_duration<E>(dur: DurationSigned) -> Result<Duration, E>
+where
+    E: de::Error,
+{
+    let secs = i64::try_from(dur.duration.as_secs()).map_err(|_| {
+        E::custom(format!(
+            "Invalid or out of range value '{}' for time::Duration",
+            dur
+        ))
+    })?;
+    let nanos = dur.duration.subsec_nanos() as i32;
+    match dur.sign {
+        Sign::Positive => Ok(Duration::new(secs, nanos)),
+        Sign::Negative => Ok(Duration::new(-secs, -nanos)),
+    }
+}
+
+fn duration_into_primitive_datetime<E>(dur: DurationSigned) -> Result<PrimitiveDateTime, E>
+where
+    E: de::Error,
+{
+    let out_of_range = || {
+        E::custom(format!(
+            "Invalid or out of range value '{}' for a date and time",
+            dur
+        ))
+    };
+    let time_dur = duration_into_time_duration::<E>(dur).map_err(|_| out_of_range())?;
+    let epoch = unix_epoch_primitive();
+    let (min, max) = datetime_bounds();
+    if time_dur < min - epoch || time_dur > max - epoch {
+        return Err(out_of_range());
+    }
+    Ok(epoch + time_dur)
+}
+
+fn duration_into_offset_datetime<E>(dur: DurationSigned) -> Result<OffsetDateTime, E>
+where
+    E: de::Error,
+{
+    duration_into_primitive_datetime(dur).map(PrimitiveDateTime::assume_utc)
+}
+
+use_duration_signed_ser!(OffsetDateTime => TimestampSeconds, TimestampSecondsWithFrac, 1);
+use_duration_signed_ser!(OffsetDateTime => TimestampMilliSeconds, TimestampMilliSecondsWithFrac, MILLIS_PER_SEC);
+use_duration_signed_ser!(OffsetDateTime => TimestampMicroSeconds, TimestampMicroSecondsWithFrac, MICROS_PER_SEC);
+use_duration_signed_ser!(OffsetDateTime => TimestampNanoSeconds, TimestampNanoSecondsWithFrac, NANOS_PER_SEC);
+use_duration_signed_de!(OffsetDateTime, duration_into_offset_datetime => TimestampSeconds, TimestampSecondsWithFrac, 1);
+use_duration_signed_de!(OffsetDateTime, duration_into_offset_datetime => TimestampMilliSeconds, TimestampMilliSecondsWithFrac, MILLIS_PER_SEC);
+use_duration_signed_de!(OffsetDateTime, duration_into_offset_datetime => TimestampMicroSeconds, TimestampMicroSecondsWithFrac, MICROS_PER_SEC);
+use_duration_signed_de!(OffsetDateTime, duration_into_offset_datetime => TimestampNanoSeconds, TimestampNanoSecondsWithFrac, NANOS_PER_SEC);
+
+use_duration_signed_ser!(PrimitiveDateTime => TimestampSeconds, TimestampSecondsWithFrac, 1);
+use_duration_signed_ser!(PrimitiveDateTime => TimestampMilliSeconds, TimestampMilliSecondsWithFrac, MILLIS_PER_SEC);
+use_duration_signed_ser!(PrimitiveDateTime => TimestampMicroSeconds, TimestampMicroSecondsWithFrac, MICROS_PER_SEC);
+use_duration_signed_ser!(PrimitiveDateTime => TimestampNanoSeconds, TimestampNanoSecondsWithFrac, NANOS_PER_SEC);
+use_duration_signed_de!(PrimitiveDateTime, duration_into_primitive_datetime => TimestampSeconds, TimestampSecondsWithFrac, 1);
+use_duration_signed_de!(PrimitiveDateTime, duration_into_primitive_datetime => TimestampMilliSeconds, TimestampMilliSecondsWithFrac, MILLIS_PER_SEC);
+use_duration_signed_de!(PrimitiveDateTime, duration_into_primitive_datetime => TimestampMicroSeconds, TimestampMicroSecondsWithFrac, MICROS_PER_SEC);
+use_duration_signed_de!(PrimitiveDateTime, duration_into_primitive_datetime => TimestampNanoSeconds, TimestampNanoSecondsWithFrac, NANOS_PER_SEC);
+
+use_duration_signed_ser!(Duration => DurationSeconds, DurationSecondsWithFrac, 1);
+use_duration_signed_ser!(Duration => DurationMilliSeconds, DurationMilliSecondsWithFrac, MILLIS_PER_SEC);
+use_duration_signed_ser!(Duration => DurationMicroSeconds, DurationMicroSecondsWithFrac, MICROS_PER_SEC);
+use_duration_signed_ser!(Duration => DurationNanoSeconds, DurationNanoSecondsWithFrac, NANOS_PER_SEC);
+use_duration_signed_de!(Duration, duration_into_time_duration => DurationSeconds, DurationSecondsWithFrac, 1);
+use_duration_signed_de!(Duration, duration_into_time_duration => DurationMilliSeconds, DurationMilliSecondsWithFrac, MILLIS_PER_SEC);
+use_duration_signed_de!(Duration, duration_into_time_duration => DurationMicroSeconds, DurationMicroSecondsWithFrac, MICROS_PER_SEC);
+use_duration_signed_de!(Duration, duration_into_time_duration => DurationNanoSeconds, DurationNanoSecondsWithFrac, NANOS_PER_SEC);
+
+impl<DESIGNATORS> SerializeAs<Duration> for Iso8601Duration<DESIGNATORS>
+where
+    DESIGNATORS: Iso8601Designators,
+{
+    fn serialize_as<S>(source: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        format_iso8601_duration(DurationSigned::from(source)).serialize(serializer)
+    }
+}
+
+impl<'de, DESIGNATORS> DeserializeAs<'de, Duration> for Iso8601Duration<DESIGNATORS>
+where
+    DESIGNATORS: Iso8601Designators,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<Duration, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        duration_into_time_duration(deserialize_iso8601_duration(
+            deserializer,
+            DESIGNATORS::DAYS_AND_WEEKS,
+        )?)
+    }
+}
+
+impl<STRICTNESS> SerializeAs<Duration> for DurationHumanString<STRICTNESS>
+where
+    STRICTNESS: Strictness,
+{
+    fn serialize_as<S>(source: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        format_human_duration(DurationSigned::from(source)).serialize(serializer)
+    }
+}
+
+impl<'de> DeserializeAs<'de, Duration> for DurationHumanString<Strict> {
+    fn deserialize_as<D>(deserializer: D) -> Result<Duration, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        duration_into_time_duration(
+            deserializer.deserialize_str(DurationHumanStringVisitor::new(false))?,
+        )
+    }
+}
+
+impl<'de> DeserializeAs<'de, Duration> for DurationHumanString<Flexible> {
+    fn deserialize_as<D>(deserializer: D) -> Result<Duration, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        duration_into_time_duration(
+            deserializer.deserialize_any(DurationHumanStringVisitor::new(true))?,
+        )
+    }
+}
@@ -0,0 +1,41 @@
+//! Byte sources and sinks shared by the text encodings of bytes, like `Hex` or `Base64`
+//!
+//! Every encoding serializes any type implementing `AsRef<[u8]>` and deserializes into any type implementing `TryFrom<Vec<u8>>`.
+//! This covers `Vec<u8>`, `[u8; N]`, `Box<[u8]>`, and `Cow<[u8]>` for all encodings alike.
+
+use serde::{de::Error, Deserialize, Deserializer, Serializer};
+use std::convert::TryFrom;
+
+/// Serialize the bytes of `source` as the string returned by `encode`
+pub(crate) fn serialize_encoded<S, T, F>(
+    source: &T,
+    encode: F,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: AsRef<[u8]> + ?Sized,
+    F: FnOnce(&[u8]) -> String,
+{
+    serializer.serialize_str(&encode(source.as_ref()))
+}
+
+/// Deserialize a string, decode it with `decode`, and convert the bytes into `T`
+///
+/// The error message returned by `decode` is used as custom error.
+pub(crate) fn deserialize_decoded<'de, D, T, F>(deserializer: D, decode: F) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: TryFrom<Vec<u8>>,
+    F: FnOnce(&str) -> Result<Vec<u8>, String>,
+{
+    let value = String::deserialize(deserializer)?;
+    let bytes = decode(&value).map_err(D::Error::custom)?;
+    let len = bytes.len();
+    T::try_from(bytes).map_err(|_| {
+        D::Error::custom(format!(
+            "Can't convert a byte vector of length {} to the output type.",
+            len
+        ))
+    })
+}
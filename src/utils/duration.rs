@@ -0,0 +1,1053 @@
+//! Internal helpers for the duration based adapters
+//!
+//! All duration-like types are converted into a [`DurationSigned`] first.
+//! The adapters only need to implement the conversion between [`DurationSigned`] and the serialized format.
+
+use crate::utils::NANOS_PER_SEC;
+use serde::{
+    de::{self, Unexpected, Visitor},
+    ser, Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    convert::TryFrom,
+    fmt,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub(crate) enum Sign {
+    Positive,
+    Negative,
+}
+
+impl Sign {
+    #[inline]
+    pub(crate) fn is_negative(self) -> bool {
+        self == Sign::Negative
+    }
+
+    #[inline]
+    pub(crate) fn apply_f64(self, value: f64) -> f64 {
+        match self {
+            Sign::Positive => value,
+            Sign::Negative => -value,
+        }
+    }
+}
+
+/// A [`Duration`] with a sign
+///
+/// Zero durations are always treated as positive, regardless of the sign.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct DurationSigned {
+    pub(crate) sign: Sign,
+    pub(crate) duration: Duration,
+}
+
+impl DurationSigned {
+    pub(crate) fn new(sign: Sign, secs: u64, nanosecs: u32) -> Self {
+        Self::with_duration(sign, Duration::new(secs, nanosecs))
+    }
+
+    pub(crate) fn with_duration(sign: Sign, duration: Duration) -> Self {
+        let sign = if duration == Duration::new(0, 0) {
+            Sign::Positive
+        } else {
+            sign
+        };
+        Self { sign, duration }
+    }
+
+    pub(crate) fn from_secs_i64(secs: i64) -> Self {
+        if secs >= 0 {
+            Self::new(Sign::Positive, secs as u64, 0)
+        } else {
+            Self::new(Sign::Negative, secs.wrapping_neg() as u64, 0)
+        }
+    }
+
+    pub(crate) fn from_secs_u64(secs: u64) -> Self {
+        Self::new(Sign::Positive, secs, 0)
+    }
+
+    /// Convert a float number of `1 / factor` seconds into seconds, keeping the sign
+    pub(crate) fn from_units_f64(value: f64, factor: u32) -> Result<Self, String> {
+        let sign = if value.is_sign_negative() {
+            Sign::Negative
+        } else {
+            Sign::Positive
+        };
+        crate::utils::duration_from_units_f64(value.abs(), factor)
+            .map(|duration| Self::with_duration(sign, duration))
+    }
+
+    /// Convert a float number of `1 / factor` seconds into seconds, which must not be negative
+    pub(crate) fn from_unsigned_units_f64(value: f64, factor: u32) -> Result<Self, String> {
+        crate::utils::duration_from_units_f64(value, factor)
+            .map(|duration| Self::with_duration(Sign::Positive, duration))
+    }
+
+    /// Convert into a [`Duration`], which requires the value to be positive
+    pub(crate) fn to_std_duration<E>(self) -> Result<Duration, E>
+    where
+        E: de::Error,
+    {
+        if self.sign.is_negative() {
+            Err(E::custom(format!(
+                "Negative values are not supported for Duration. Found {}",
+                self
+            )))
+        } else {
+            Ok(self.duration)
+        }
+    }
+
+    /// Interpret the value as offset from the Unix epoch
+    pub(crate) fn to_system_time<E>(self) -> Result<SystemTime, E>
+    where
+        E: de::Error,
+    {
+        let time = match self.sign {
+            Sign::Positive => UNIX_EPOCH.checked_add(self.duration),
+            Sign::Negative => UNIX_EPOCH.checked_sub(self.duration),
+        };
+        time.ok_or_else(|| {
+            E::custom(format!(
+                "Timestamp of {} seconds cannot be represented as SystemTime",
+                self
+            ))
+        })
+    }
+
+    pub(crate) fn checked_mul(self, rhs: u32) -> Option<Self> {
+        self.duration
+            .checked_mul(rhs)
+            .map(|duration| Self::with_duration(self.sign, duration))
+    }
+
+    /// Convert the value from a different unit, for example `1000` for milliseconds
+    pub(crate) fn unit_to_secs(self, factor: u32) -> Self {
+        Self::with_duration(self.sign, self.duration / factor)
+    }
+
+    /// Convert the value into a different unit, for example `1000` for milliseconds
+    ///
+    /// Fails if the value cannot be represented anymore.
+    pub(crate) fn to_unit<E>(self, factor: u32) -> Result<Self, E>
+    where
+        E: ser::Error,
+    {
+        self.checked_mul(factor).ok_or_else(|| {
+            E::custom("Failed to serialize value as the value cannot be represented.")
+        })
+    }
+
+    pub(crate) fn to_secs_f64(self) -> f64 {
+        self.sign
+            .apply_f64(crate::utils::duration_as_secs_f64(&self.duration))
+    }
+
+    /// Round to the nearest full second, rounding half away from zero
+    pub(crate) fn round_secs(self) -> Option<Self> {
+        let mut secs = self.duration.as_secs();
+        if self.duration.subsec_nanos() >= NANOS_PER_SEC / 2 {
+            secs = secs.checked_add(1)?;
+        }
+        Some(Self::new(self.sign, secs, 0))
+    }
+
+    /// Serialize the value as rounded integer number of seconds
+    pub(crate) fn serialize_integer<S>(self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let rounded = self.round_secs().ok_or_else(|| {
+            ser::Error::custom("Failed to serialize value as the value cannot be represented.")
+        })?;
+        let secs = rounded.duration.as_secs();
+        if rounded.sign.is_negative() {
+            if secs > 1 << 63 {
+                return Err(ser::Error::custom(
+                    "Failed to serialize value as the value cannot be represented.",
+                ));
+            }
+            (secs as i64).wrapping_neg().serialize(serializer)
+        } else {
+            secs.serialize(serializer)
+        }
+    }
+
+    /// Serialize the value as rounded float number of seconds
+    pub(crate) fn serialize_f64_rounded<S>(self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.to_secs_f64().round().serialize(serializer)
+    }
+
+    /// Serialize the value as rounded integer number of seconds inside a string
+    pub(crate) fn serialize_string_rounded<S>(self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let rounded = self.round_secs().ok_or_else(|| {
+            ser::Error::custom("Failed to serialize value as the value cannot be represented.")
+        })?;
+        rounded.to_string().serialize(serializer)
+    }
+
+    /// Serialize the value as float number of seconds
+    pub(crate) fn serialize_f64<S>(self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.to_secs_f64().serialize(serializer)
+    }
+
+    /// Serialize the value as exact decimal number of seconds inside a string
+    pub(crate) fn serialize_string<S>(self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.to_string().serialize(serializer)
+    }
+}
+
+impl From<&Duration> for DurationSigned {
+    fn from(&duration: &Duration) -> Self {
+        Self::with_duration(Sign::Positive, duration)
+    }
+}
+
+/// Times before the Unix epoch are negative
+impl From<&SystemTime> for DurationSigned {
+    fn from(time: &SystemTime) -> Self {
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(dur) => Self::with_duration(Sign::Positive, dur),
+            Err(err) => Self::with_duration(Sign::Negative, err.duration()),
+        }
+    }
+}
+
+/// Format the value as exact decimal number of seconds, without trailing zeros
+impl fmt::Display for DurationSigned {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.sign.is_negative() {
+            f.write_str("-")?;
+        }
+        write!(f, "{}", self.duration.as_secs())?;
+        let nanos = self.duration.subsec_nanos();
+        if nanos > 0 {
+            let subsecs = format!("{:09}", nanos);
+            write!(f, ".{}", subsecs.trim_end_matches('0'))?;
+        }
+        Ok(())
+    }
+}
+
+/// Deserialize a duration from an integer, a float, or a string containing a number
+///
+/// The value is interpreted in units of `1 / factor` seconds, e.g., a factor of `1000` deserializes milliseconds.
+/// The returned value is converted into seconds.
+/// Errors still report the value as it was found in the input.
+/// Negative values are only accepted if the visitor is created with [`DurationVisitiorFlexible::new_signed`].
+/// `adapter` is the name of the adapter type, like `TimestampMilliSeconds`, which is used in error messages.
+pub(crate) struct DurationVisitiorFlexible {
+    factor: u32,
+    signed: bool,
+    adapter: &'static str,
+}
+
+impl DurationVisitiorFlexible {
+    pub(crate) fn new(factor: u32, adapter: &'static str) -> Self {
+        Self {
+            factor,
+            signed: false,
+            adapter,
+        }
+    }
+
+    pub(crate) fn new_signed(factor: u32, adapter: &'static str) -> Self {
+        Self {
+            factor,
+            signed: true,
+            adapter,
+        }
+    }
+
+    /// Number of digits after the decimal point, which can still be represented with nanosecond precision
+    fn max_subsec_digits(&self) -> u32 {
+        let mut digits = 9;
+        let mut factor = self.factor;
+        while factor >= 10 {
+            factor /= 10;
+            digits -= 1;
+        }
+        digits
+    }
+
+    /// Reject negative values, if necessary, and convert the value into seconds
+    fn finish<E>(&self, value: DurationSigned) -> Result<DurationSigned, E>
+    where
+        E: de::Error,
+    {
+        if !self.signed {
+            value.to_std_duration::<E>()?;
+        }
+        Ok(value.unit_to_secs(self.factor))
+    }
+}
+
+impl<'de> Visitor<'de> for DurationVisitiorFlexible {
+    type Value = DurationSigned;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("an integer, a float, or a string containing a number")
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.finish(DurationSigned::from_secs_i64(value))
+    }
+
+    fn visit_u64<E>(self, secs: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.finish(DurationSigned::new(Sign::Positive, secs, 0))
+    }
+
+    fn visit_f64<E>(self, secs: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        // A float can exceed the range of `DurationSigned` in small units, so convert it into seconds directly
+        let value = DurationSigned::from_units_f64(secs, self.factor).map_err(de::Error::custom)?;
+        if !self.signed && value.sign.is_negative() {
+            // Report the value in the units of the input
+            DurationSigned::from_units_f64(secs, 1)
+                .map_err(de::Error::custom)?
+                .to_std_duration::<E>()?;
+        }
+        Ok(value)
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let mut chars = value.chars();
+        let (sign, unsigned) = if chars.next() == Some('-') {
+            (Sign::Negative, chars.as_str())
+        } else {
+            (Sign::Positive, value)
+        };
+        // `u64::from_str` accepts a leading `+`, which must not follow the `-`
+        if unsigned.starts_with('+') && sign == Sign::Negative {
+            return Err(de::Error::invalid_value(Unexpected::Str(value), &self));
+        }
+        let parts: Vec<_> = unsigned.split('.').collect();
+
+        match *parts.as_slice() {
+            [seconds] => {
+                if let Ok(seconds) = seconds.parse::<u64>() {
+                    self.finish(DurationSigned::new(sign, seconds, 0))
+                } else {
+                    Err(de::Error::invalid_value(Unexpected::Str(value), &self))
+                }
+            }
+            [seconds, subseconds] => {
+                if let Ok(seconds) = seconds.parse::<u64>() {
+                    let max_digits = self.max_subsec_digits();
+                    let subseclen = subseconds.chars().count() as u32;
+                    if subseclen > max_digits {
+                        return Err(de::Error::custom(if max_digits == 0 {
+                            format!(
+                                "{} only supports whole numbers but '{}' has a fractional part.",
+                                self.adapter, value
+                            )
+                        } else {
+                            format!(
+                                "{} only supports nanosecond precision but '{}' has more than {} fractional digits.",
+                                self.adapter,
+                                value,
+                                max_digits
+                            )
+                        }));
+                    }
+
+                    if subseconds.starts_with('+') {
+                        return Err(de::Error::invalid_value(Unexpected::Str(value), &self));
+                    }
+                    if let Ok(mut subseconds) = subseconds.parse::<u32>() {
+                        // convert subseconds to nanoseconds (10^-9), require 9 places for nanoseconds
+                        subseconds *= 10u32.pow(9 - subseclen);
+                        self.finish(DurationSigned::new(sign, seconds, subseconds))
+                    } else {
+                        Err(de::Error::invalid_value(Unexpected::Str(value), &self))
+                    }
+                } else {
+                    Err(de::Error::invalid_value(Unexpected::Str(value), &self))
+                }
+            }
+
+            _ => Err(de::Error::invalid_value(Unexpected::Str(value), &self)),
+        }
+    }
+}
+
+/// Parse a decimal number like `1.5` as a multiple of `unit_nanos` nanoseconds
+///
+/// `name` describes the value in error messages.
+/// Fails if the value cannot be represented exactly with nanosecond precision.
+fn parse_decimal_nanos(number: &str, unit_nanos: u128, name: &str) -> Result<u128, String> {
+    let mut number_parts = number.splitn(2, &['.', ','][..]);
+    let whole = number_parts.next().unwrap_or("");
+    let fraction = number_parts.next();
+    if whole.is_empty() {
+        return Err(format!("missing value for {}", name));
+    }
+    let whole: u64 = whole
+        .parse()
+        .map_err(|_| format!("invalid value for {}", name))?;
+    let mut nanos = u128::from(whole) * unit_nanos;
+
+    if let Some(fraction) = fraction {
+        let fraction_len = fraction.chars().count() as u32;
+        if fraction_len == 0 || fraction_len > 9 {
+            return Err(format!(
+                "the fraction of {} must have between 1 and 9 digits",
+                name
+            ));
+        }
+        let fraction: u32 = fraction
+            .parse()
+            .map_err(|_| format!("invalid value for {}", name))?;
+        let scale = 10u128.pow(fraction_len);
+        let fraction_nanos = u128::from(fraction) * unit_nanos / scale;
+        if fraction_nanos * scale != u128::from(fraction) * unit_nanos {
+            return Err(format!(
+                "the value for {} is more precise than a nanosecond",
+                name
+            ));
+        }
+        nanos += fraction_nanos;
+    }
+    Ok(nanos)
+}
+
+/// A component of an ISO 8601 duration, like the hours in `PT1H`
+struct Iso8601Component {
+    designator: char,
+    name: &'static str,
+    /// Length of the component in seconds, `None` if the length is ambiguous
+    secs: Option<u64>,
+    /// Only allowed if day and week designators are enabled
+    is_day: bool,
+}
+
+const ISO8601_DATE_COMPONENTS: &[Iso8601Component] = &[
+    Iso8601Component {
+        designator: 'Y',
+        name: "years",
+        secs: None,
+        is_day: false,
+    },
+    Iso8601Component {
+        designator: 'M',
+        name: "months",
+        secs: None,
+        is_day: false,
+    },
+    Iso8601Component {
+        designator: 'W',
+        name: "weeks",
+        secs: Some(7 * 24 * 60 * 60),
+        is_day: true,
+    },
+    Iso8601Component {
+        designator: 'D',
+        name: "days",
+        secs: Some(24 * 60 * 60),
+        is_day: true,
+    },
+];
+
+const ISO8601_TIME_COMPONENTS: &[Iso8601Component] = &[
+    Iso8601Component {
+        designator: 'H',
+        name: "hours",
+        secs: Some(60 * 60),
+        is_day: false,
+    },
+    Iso8601Component {
+        designator: 'M',
+        name: "minutes",
+        secs: Some(60),
+        is_day: false,
+    },
+    Iso8601Component {
+        designator: 'S',
+        name: "seconds",
+        secs: Some(1),
+        is_day: false,
+    },
+];
+
+/// Parse an ISO 8601 duration like `PT1H30M5.25S` or `-P3DT4H`
+///
+/// Years and months are always rejected, as their length depends on the calendar.
+/// Weeks and days are only accepted if `allow_days` is set and count as 7 and 1 times 24 hours.
+/// Only the last component may have a fractional part.
+pub(crate) fn parse_iso8601_duration(
+    value: &str,
+    allow_days: bool,
+) -> Result<DurationSigned, String> {
+    let error = |msg: String| format!("Invalid ISO 8601 duration '{}': {}", value, msg);
+
+    let mut chars = value.chars();
+    let (sign, unsigned) = if chars.next() == Some('-') {
+        (Sign::Negative, chars.as_str())
+    } else {
+        (Sign::Positive, value)
+    };
+    let mut chars = unsigned.chars();
+    if chars.next() != Some('P') {
+        return Err(error("expected the duration to start with 'P'".into()));
+    }
+    let unsigned = chars.as_str();
+    let (date, time) = match unsigned.find('T') {
+        Some(idx) => (&unsigned[..idx], Some(&unsigned[idx + 1..])),
+        None => (unsigned, None),
+    };
+    if time == Some("") {
+        return Err(error(
+            "expected at least one component after the time designator 'T'".into(),
+        ));
+    }
+    if date.is_empty() && time.is_none() {
+        return Err(error("expected at least one component".into()));
+    }
+
+    let mut total_nanos: u128 = 0;
+    let mut has_fraction = false;
+    let parts = [
+        (date, ISO8601_DATE_COMPONENTS),
+        (time.unwrap_or(""), ISO8601_TIME_COMPONENTS),
+    ];
+    for &(mut part, components) in &parts {
+        // Index of the next allowed component, which ensures the order and uniqueness of the components
+        let mut next_component = 0;
+        while !part.is_empty() {
+            let number_len = part
+                .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))
+                .ok_or_else(|| error(format!("missing designator after '{}'", part)))?;
+            let (number, rest) = part.split_at(number_len);
+            let mut rest_chars = rest.chars();
+            let designator = rest_chars.next().expect("rest is never empty");
+            part = rest_chars.as_str();
+
+            let idx = components
+                .iter()
+                .position(|c| c.designator == designator)
+                .ok_or_else(|| error(format!("unknown designator '{}'", designator)))?;
+            let component = &components[idx];
+            if idx < next_component {
+                return Err(error(format!(
+                    "{} are repeated or in the wrong order",
+                    component.name
+                )));
+            }
+            next_component = idx + 1;
+
+            let unit_secs = component.secs.ok_or_else(|| {
+                error(format!(
+                    "{} are not supported, as their length is ambiguous",
+                    component.name
+                ))
+            })?;
+            if component.is_day && !allow_days {
+                return Err(error(format!("{} are not allowed", component.name)));
+            }
+            if has_fraction {
+                return Err(error(format!(
+                    "{} follow a fractional component, but only the last component may have a fractional part",
+                    component.name
+                )));
+            }
+
+            let nanos = parse_decimal_nanos(
+                number,
+                u128::from(unit_secs) * u128::from(NANOS_PER_SEC),
+                component.name,
+            )
+            .map_err(error)?;
+            has_fraction = number.contains(&['.', ','][..]);
+
+            total_nanos = total_nanos
+                .checked_add(nanos)
+                .ok_or_else(|| error("the value is too large".into()))?;
+        }
+    }
+
+    let secs = u64::try_from(total_nanos / u128::from(NANOS_PER_SEC))
+        .map_err(|_| error("the value is too large".into()))?;
+    Ok(DurationSigned::new(
+        sign,
+        secs,
+        (total_nanos % u128::from(NANOS_PER_SEC)) as u32,
+    ))
+}
+
+/// Deserialize a string containing an ISO 8601 duration, see [`parse_iso8601_duration`]
+pub(crate) fn deserialize_iso8601_duration<'de, D>(
+    deserializer: D,
+    allow_days: bool,
+) -> Result<DurationSigned, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    parse_iso8601_duration(&value, allow_days).map_err(de::Error::custom)
+}
+
+/// Format the value as canonical ISO 8601 duration
+///
+/// Only the hours, minutes, and seconds components are used, as days are ambiguous in calendar time.
+pub(crate) fn format_iso8601_duration(dur: DurationSigned) -> String {
+    let mut output = String::new();
+    if dur.sign.is_negative() {
+        output.push('-');
+    }
+    output.push_str("PT");
+
+    let secs = dur.duration.as_secs();
+    let hours = secs / (60 * 60);
+    let minutes = secs / 60 % 60;
+    let seconds = DurationSigned::new(Sign::Positive, secs % 60, dur.duration.subsec_nanos());
+    if hours > 0 {
+        output.push_str(&format!("{}H", hours));
+    }
+    if minutes > 0 {
+        output.push_str(&format!("{}M", minutes));
+    }
+    if seconds.duration > Duration::new(0, 0) || (hours == 0 && minutes == 0) {
+        output.push_str(&format!("{}S", seconds));
+    }
+    output
+}
+
+/// A unit of a human-readable duration, like the `h` in `1h30m`
+struct HumanUnit {
+    suffix: &'static str,
+    name: &'static str,
+    nanos: u64,
+    /// Units which are a power of ten of a second can be serialized with a fraction
+    ///
+    /// Parsing accepts a fraction for every unit.
+    is_decimal: bool,
+}
+
+/// All supported units, from largest to smallest
+const HUMAN_UNITS: &[HumanUnit] = &[
+    HumanUnit {
+        suffix: "d",
+        name: "days",
+        nanos: 24 * 60 * 60 * 1_000_000_000,
+        is_decimal: false,
+    },
+    HumanUnit {
+        suffix: "h",
+        name: "hours",
+        nanos: 60 * 60 * 1_000_000_000,
+        is_decimal: false,
+    },
+    HumanUnit {
+        suffix: "m",
+        name: "minutes",
+        nanos: 60 * 1_000_000_000,
+        is_decimal: false,
+    },
+    HumanUnit {
+        suffix: "s",
+        name: "seconds",
+        nanos: 1_000_000_000,
+        is_decimal: true,
+    },
+    HumanUnit {
+        suffix: "ms",
+        name: "milliseconds",
+        nanos: 1_000_000,
+        is_decimal: true,
+    },
+    HumanUnit {
+        suffix: "us",
+        name: "microseconds",
+        nanos: 1_000,
+        is_decimal: true,
+    },
+    HumanUnit {
+        suffix: "ns",
+        name: "nanoseconds",
+        nanos: 1,
+        is_decimal: true,
+    },
+];
+
+/// Parse a human-readable duration like `30s`, `1h 15m`, or `250ms`
+///
+/// The components must be ordered from the largest to the smallest unit.
+/// Every component may have a fraction, like `1.5h`, independent of [`HumanUnit::is_decimal`].
+/// Whitespace is allowed between and within the components.
+pub(crate) fn parse_human_duration(value: &str) -> Result<DurationSigned, String> {
+    let error = |msg: String| format!("Invalid duration '{}': {}", value, msg);
+
+    let mut chars = value.trim_start().chars();
+    let (sign, mut rest) = if chars.next() == Some('-') {
+        (Sign::Negative, chars.as_str())
+    } else {
+        (Sign::Positive, value)
+    };
+
+    let mut total_nanos: u128 = 0;
+    let mut has_component = false;
+    // Index of the next allowed unit, which ensures the order and uniqueness of the components
+    let mut next_unit = 0;
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let (number, after_number) = rest.split_at(number_len);
+        if number.is_empty() {
+            return Err(error(format!("expected a number, found '{}'", rest)));
+        }
+        let after_number = after_number.trim_start();
+        let suffix_len = after_number
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(after_number.len());
+        let (suffix, after_suffix) = after_number.split_at(suffix_len);
+        rest = after_suffix;
+        if suffix.is_empty() {
+            return Err(error(format!("missing unit after '{}'", number)));
+        }
+
+        let idx = HUMAN_UNITS
+            .iter()
+            .position(|unit| unit.suffix == suffix)
+            .ok_or_else(|| error(format!("unknown unit '{}'", suffix)))?;
+        let unit = &HUMAN_UNITS[idx];
+        if idx < next_unit {
+            return Err(error(format!(
+                "{} are repeated or in the wrong order",
+                unit.name
+            )));
+        }
+        next_unit = idx + 1;
+
+        let nanos =
+            parse_decimal_nanos(number, u128::from(unit.nanos), unit.name).map_err(error)?;
+        total_nanos = total_nanos
+            .checked_add(nanos)
+            .ok_or_else(|| error("the value is too large".into()))?;
+        has_component = true;
+    }
+    if !has_component {
+        return Err(error("expected at least one component".into()));
+    }
+
+    let secs = u64::try_from(total_nanos / u128::from(NANOS_PER_SEC))
+        .map_err(|_| error("the value is too large".into()))?;
+    Ok(DurationSigned::new(
+        sign,
+        secs,
+        (total_nanos % u128::from(NANOS_PER_SEC)) as u32,
+    ))
+}
+
+/// Format the value as the shortest exact human-readable duration
+///
+/// Every component uses a smaller unit than the previous one.
+/// Only the last component may have a fraction, which requires a unit which is a power of ten of a second.
+/// If two representations have the same length, the one without a fraction is preferred.
+pub(crate) fn format_human_duration(dur: DurationSigned) -> String {
+    let total_nanos = u128::from(dur.duration.as_secs()) * u128::from(NANOS_PER_SEC)
+        + u128::from(dur.duration.subsec_nanos());
+    let sign = if dur.sign.is_negative() { "-" } else { "" };
+    if total_nanos == 0 {
+        return "0s".into();
+    }
+
+    let mut best: Option<String> = None;
+    for first in 0..HUMAN_UNITS.len() {
+        for last in first..HUMAN_UNITS.len() {
+            let mut candidate = sign.to_string();
+            let mut remaining = total_nanos;
+            for unit in &HUMAN_UNITS[first..last] {
+                let unit_nanos = u128::from(unit.nanos);
+                if remaining >= unit_nanos {
+                    candidate.push_str(&format!("{}{}", remaining / unit_nanos, unit.suffix));
+                    remaining %= unit_nanos;
+                }
+            }
+
+            let unit = &HUMAN_UNITS[last];
+            let unit_nanos = u128::from(unit.nanos);
+            if remaining % unit_nanos != 0 && !unit.is_decimal {
+                continue;
+            }
+            if remaining > 0 {
+                let fraction = remaining % unit_nanos;
+                candidate.push_str(&(remaining / unit_nanos).to_string());
+                if fraction > 0 {
+                    let digits = unit.nanos.to_string().len() - 1;
+                    let fraction = format!("{:0width$}", fraction, width = digits);
+                    candidate.push('.');
+                    candidate.push_str(fraction.trim_end_matches('0'));
+                }
+                candidate.push_str(unit.suffix);
+            }
+
+            let is_better = match best {
+                None => true,
+                Some(ref best) => {
+                    (candidate.len(), candidate.contains('.')) < (best.len(), best.contains('.'))
+                }
+            };
+            if is_better {
+                best = Some(candidate);
+            }
+        }
+    }
+    best.expect("The nanosecond representation is always exact")
+}
+
+/// Deserialize a human-readable duration, see [`parse_human_duration`]
+///
+/// With `allow_numbers`, plain numbers of seconds are accepted too, as in [`DurationVisitiorFlexible`].
+pub(crate) struct DurationHumanStringVisitor {
+    allow_numbers: bool,
+}
+
+impl DurationHumanStringVisitor {
+    pub(crate) fn new(allow_numbers: bool) -> Self {
+        Self { allow_numbers }
+    }
+}
+
+impl<'de> Visitor<'de> for DurationHumanStringVisitor {
+    type Value = DurationSigned;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.allow_numbers {
+            formatter.write_str("a duration string like \"1h30m\" or a number of seconds")
+        } else {
+            formatter.write_str("a duration string like \"1h30m\"")
+        }
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        if self.allow_numbers {
+            DurationVisitiorFlexible::new_signed(1, "DurationHumanString").visit_i64(value)
+        } else {
+            Err(de::Error::invalid_type(Unexpected::Signed(value), &self))
+        }
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        if self.allow_numbers {
+            DurationVisitiorFlexible::new_signed(1, "DurationHumanString").visit_u64(value)
+        } else {
+            Err(de::Error::invalid_type(Unexpected::Unsigned(value), &self))
+        }
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        if self.allow_numbers {
+            DurationVisitiorFlexible::new_signed(1, "DurationHumanString").visit_f64(value)
+        } else {
+            Err(de::Error::invalid_type(Unexpected::Float(value), &self))
+        }
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        parse_human_duration(value).or_else(|err| {
+            if self.allow_numbers {
+                DurationVisitiorFlexible::new_signed(1, "DurationHumanString")
+                    .visit_str::<E>(value)
+                    .map_err(|_| de::Error::custom(err))
+            } else {
+                Err(de::Error::custom(err))
+            }
+        })
+    }
+}
+
+/// Implement `SerializeAs` for a duration-like type, which can be converted into a [`DurationSigned`]
+///
+/// `$ty` and `$ty_frac` are the adapters for whole and fractional values.
+/// `$factor` is the number of units per second.
+macro_rules! use_duration_signed_ser {
+    ($source:ty => $ty:ident, $ty_frac:ident, $factor:expr) => {
+        use_duration_signed_ser!(@impl $source => $ty, $factor, {
+            $crate::Integer => serialize_integer,
+            f64 => serialize_f64_rounded,
+            String => serialize_string_rounded,
+        });
+        use_duration_signed_ser!(@impl $source => $ty_frac, $factor, {
+            f64 => serialize_f64,
+            String => serialize_string,
+        });
+    };
+    (@impl $source:ty => $ty:ident, $factor:expr, { $($format:ty => $method:ident),* $(,)? }) => {$(
+        impl<STRICTNESS> $crate::ser::SerializeAs<$source> for $crate::$ty<$format, STRICTNESS>
+        where
+            STRICTNESS: $crate::Strictness,
+        {
+            fn serialize_as<S>(source: &$source, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: $crate::serde::Serializer,
+            {
+                $crate::utils::duration::DurationSigned::from(source)
+                    .to_unit::<S::Error>($factor)?
+                    .$method(serializer)
+            }
+        }
+    )*};
+}
+
+/// Implement `DeserializeAs` for a duration-like type, which can be created from a [`DurationSigned`]
+///
+/// `$to_target` converts the [`DurationSigned`] into the target type and is called with the value in seconds.
+/// `$ty` and `$ty_frac` are the adapters for whole and fractional values.
+/// `$factor` is the number of units per second.
+/// Targets which cannot be negative, like [`Duration`], use the `unsigned` variant.
+/// It rejects negative input before the conversion, such that errors report the value as found in the input.
+macro_rules! use_duration_signed_de {
+    ($target:ty, $to_target:path => $ty:ident, $ty_frac:ident, $factor:expr) => {
+        use_duration_signed_de!(@impl $target, $to_target, i64, from_secs_i64, from_units_f64, new_signed => $ty, $ty_frac, $factor);
+    };
+    ($target:ty, $to_target:path, unsigned => $ty:ident, $ty_frac:ident, $factor:expr) => {
+        use_duration_signed_de!(@impl $target, $to_target, u64, from_secs_u64, from_unsigned_units_f64, new => $ty, $ty_frac, $factor);
+    };
+    (@impl $target:ty, $to_target:path, $integer:ty, $from_integer:ident, $from_f64:ident, $visitor:ident => $ty:ident, $ty_frac:ident, $factor:expr) => {
+        impl<'de> $crate::de::DeserializeAs<'de, $target>
+            for $crate::$ty<$crate::Integer, $crate::Strict>
+        {
+            fn deserialize_as<D>(deserializer: D) -> Result<$target, D::Error>
+            where
+                D: $crate::serde::Deserializer<'de>,
+            {
+                let value = <$integer as $crate::serde::Deserialize<'de>>::deserialize(deserializer)?;
+                $to_target(
+                    $crate::utils::duration::DurationSigned::$from_integer(value)
+                        .unit_to_secs($factor),
+                )
+            }
+        }
+
+        impl<'de> $crate::de::DeserializeAs<'de, $target> for $crate::$ty<f64, $crate::Strict> {
+            fn deserialize_as<D>(deserializer: D) -> Result<$target, D::Error>
+            where
+                D: $crate::serde::Deserializer<'de>,
+            {
+                let value = <f64 as $crate::serde::Deserialize<'de>>::deserialize(deserializer)?;
+                let dur = $crate::utils::duration::DurationSigned::$from_f64(value, $factor)
+                    .map_err(<D::Error as $crate::serde::de::Error>::custom)?;
+                $to_target(dur)
+            }
+        }
+
+        impl<'de> $crate::de::DeserializeAs<'de, $target> for $crate::$ty<String, $crate::Strict> {
+            fn deserialize_as<D>(deserializer: D) -> Result<$target, D::Error>
+            where
+                D: $crate::serde::Deserializer<'de>,
+            {
+                let value: $integer = $crate::rust::display_fromstr::deserialize(deserializer)?;
+                $to_target(
+                    $crate::utils::duration::DurationSigned::$from_integer(value)
+                        .unit_to_secs($factor),
+                )
+            }
+        }
+
+        impl<'de, FORMAT> $crate::de::DeserializeAs<'de, $target>
+            for $crate::$ty<FORMAT, $crate::Flexible>
+        where
+            FORMAT: $crate::Format,
+        {
+            fn deserialize_as<D>(deserializer: D) -> Result<$target, D::Error>
+            where
+                D: $crate::serde::Deserializer<'de>,
+            {
+                let dur = deserializer.deserialize_any(
+                    $crate::utils::duration::DurationVisitiorFlexible::$visitor($factor, stringify!($ty)),
+                )?;
+                $to_target(dur)
+            }
+        }
+
+        impl<'de> $crate::de::DeserializeAs<'de, $target>
+            for $crate::$ty_frac<f64, $crate::Strict>
+        {
+            fn deserialize_as<D>(deserializer: D) -> Result<$target, D::Error>
+            where
+                D: $crate::serde::Deserializer<'de>,
+            {
+                let value = <f64 as $crate::serde::Deserialize<'de>>::deserialize(deserializer)?;
+                let dur = $crate::utils::duration::DurationSigned::$from_f64(value, $factor)
+                    .map_err(<D::Error as $crate::serde::de::Error>::custom)?;
+                $to_target(dur)
+            }
+        }
+
+        impl<'de> $crate::de::DeserializeAs<'de, $target>
+            for $crate::$ty_frac<String, $crate::Strict>
+        {
+            fn deserialize_as<D>(deserializer: D) -> Result<$target, D::Error>
+            where
+                D: $crate::serde::Deserializer<'de>,
+            {
+                let value = <String as $crate::serde::Deserialize<'de>>::deserialize(deserializer)?;
+                let dur = $crate::serde::de::Visitor::visit_str::<D::Error>(
+                    $crate::utils::duration::DurationVisitiorFlexible::$visitor($factor, stringify!($ty_frac)),
+                    &*value,
+                )?;
+                $to_target(dur)
+            }
+        }
+
+        impl<'de, FORMAT> $crate::de::DeserializeAs<'de, $target>
+            for $crate::$ty_frac<FORMAT, $crate::Flexible>
+        where
+            FORMAT: $crate::Format,
+        {
+            fn deserialize_as<D>(deserializer: D) -> Result<$target, D::Error>
+            where
+                D: $crate::serde::Deserializer<'de>,
+            {
+                let dur = deserializer.deserialize_any(
+                    $crate::utils::duration::DurationVisitiorFlexible::$visitor($factor, stringify!($ty_frac)),
+                )?;
+                $to_target(dur)
+            }
+        }
+    };
+}
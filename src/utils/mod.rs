@@ -2,6 +2,22 @@ use crate::de::{impls::DeserializeAsWrap, DeserializeAs};
 use serde::de::{MapAccess, SeqAccess};
 use std::marker::PhantomData;
 
+#[cfg(any(
+    feature = "base32",
+    feature = "base58",
+    feature = "base64",
+    feature = "hex"
+))]
+pub(crate) mod bytes;
+pub(crate) mod content;
+#[macro_use]
+pub(crate) mod duration;
+
+/// Adapter for string types, which are serialized using `AsRef<str>`
+///
+/// This allows [`NoneAsEmptyString`](crate::NoneAsEmptyString) to share the serialization of [`NoneAs`](crate::NoneAs).
+pub(crate) struct StrLike;
+
 /// Re-Implementation of `serde::private::de::size_hint::cautious`
 #[inline]
 pub(crate) fn size_hint_cautious(hint: Option<usize>) -> usize {
@@ -11,8 +27,8 @@ pub(crate) fn size_hint_cautious(hint: Option<usize>) -> usize {
 pub(crate) const NANOS_PER_SEC: u32 = 1_000_000_000;
 // pub(crate) const NANOS_PER_MILLI: u32 = 1_000_000;
 // pub(crate) const NANOS_PER_MICRO: u32 = 1_000;
-// pub(crate) const MILLIS_PER_SEC: u64 = 1_000;
-// pub(crate) const MICROS_PER_SEC: u64 = 1_000_000;
+pub(crate) const MILLIS_PER_SEC: u32 = 1_000;
+pub(crate) const MICROS_PER_SEC: u32 = 1_000_000;
 
 pub(crate) struct MapIter<'de, A, K, KAs, V, VAs> {
     pub(crate) access: A,
@@ -94,11 +110,18 @@ pub(crate) fn duration_as_secs_f64(dur: &std::time::Duration) -> f64 {
     (dur.as_secs() as f64) + (dur.subsec_nanos() as f64) / (NANOS_PER_SEC as f64)
 }
 
-pub(crate) fn duration_from_secs_f64(secs: f64) -> Result<std::time::Duration, String> {
+/// Convert a float number of `1 / factor` seconds, for example milliseconds with a factor of `1000`
+///
+/// The value is scaled to nanoseconds in a single step.
+/// This keeps large counts of small units, like `u64::MAX` nanoseconds, in range and whole numbers exact.
+pub(crate) fn duration_from_units_f64(
+    value: f64,
+    factor: u32,
+) -> Result<std::time::Duration, String> {
     const MAX_NANOS_F64: f64 = ((u64::max_value() as u128 + 1) * (NANOS_PER_SEC as u128)) as f64;
     // TODO why are the seconds converted to nanoseconds first?
     // Does it make sense to just truncate the value?
-    let nanos = secs * (NANOS_PER_SEC as f64);
+    let nanos = value * f64::from(NANOS_PER_SEC / factor);
     if !nanos.is_finite() {
         return Err("got non-finite value when converting float to duration".into());
     }
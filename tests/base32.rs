@@ -0,0 +1,76 @@
+#![cfg(feature = "base32")]
+
+mod utils;
+
+use crate::utils::{check_deserialization, check_error_deserialization, is_equal};
+use serde::{Deserialize, Serialize};
+use serde_with::{
+    base32::{Base32, Crockford, Rfc4648Hex},
+    As, Unpadded,
+};
+
+#[test]
+fn base32_rfc4648() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    pub struct SomeBytes {
+        #[serde(with = "As::<Vec<Base32>>")]
+        bytes: Vec<Vec<u8>>,
+    }
+    is_equal(
+        SomeBytes {
+            bytes: vec![b"foobar".to_vec(), vec![0, 1, 2, 13], b"f".to_vec(), vec![]],
+        },
+        r#"{"bytes":["MZXW6YTBOI======","AAAQEDI=","MY======",""]}"#,
+    );
+
+    // Missing padding and lowercase letters are accepted
+    check_deserialization(
+        SomeBytes {
+            bytes: vec![b"foobar".to_vec()],
+        },
+        r#"{"bytes":["mzxw6ytboi"]}"#,
+    );
+    check_error_deserialization::<SomeBytes>(
+        r#"{"bytes":["MZXW1YTBOI"]}"#,
+        r#"Invalid base32 string: invalid character '1' at offset 4 at line 1 column 23"#,
+    );
+    check_error_deserialization::<SomeBytes>(
+        r#"{"bytes":["MZX"]}"#,
+        r#"Invalid base32 string: invalid length at line 1 column 16"#,
+    );
+}
+
+#[test]
+fn base32_other_alphabets() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    pub struct SomeBytes {
+        #[serde(with = "As::<Base32<Rfc4648Hex>>")]
+        hex: Vec<u8>,
+        #[serde(with = "As::<Base32<Crockford, Unpadded>>")]
+        crockford: [u8; 6],
+        #[serde(with = "As::<Base32<Crockford, Unpadded>>")]
+        boxed: Box<[u8]>,
+    }
+    is_equal(
+        SomeBytes {
+            hex: b"foobar".to_vec(),
+            crockford: *b"foobar",
+            boxed: vec![0, 1, 2, 13].into_boxed_slice(),
+        },
+        r#"{"hex":"CPNMUOJ1E8======","crockford":"CSQPYRK1E8","boxed":"000G438"}"#,
+    );
+
+    // Crockford's decoding is lenient with ambiguous characters and hyphens
+    check_deserialization(
+        SomeBytes {
+            hex: vec![],
+            crockford: *b"foobar",
+            boxed: vec![0, 1, 2, 13].into_boxed_slice(),
+        },
+        r#"{"hex":"","crockford":"csqp-yrki-e8","boxed":"oooG438"}"#,
+    );
+    check_error_deserialization::<SomeBytes>(
+        r#"{"hex":"","crockford":"CSQPYRK1","boxed":""}"#,
+        r#"Can't convert a byte vector of length 5 to the output type. at line 1 column 32"#,
+    );
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "base58")]
+
+mod utils;
+
+use crate::utils::{check_error_deserialization, is_equal};
+use serde::{Deserialize, Serialize};
+use serde_with::{
+    base58::{Base58, Base58Check},
+    As,
+};
+
+#[test]
+fn base58() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    pub struct SomeBytes {
+        #[serde(with = "As::<Vec<Base58>>")]
+        bytes: Vec<Vec<u8>>,
+    }
+    is_equal(
+        SomeBytes {
+            bytes: vec![b"Hello World!".to_vec(), vec![0, 0, 0xff], vec![]],
+        },
+        r#"{"bytes":["2NEpo7TZRRrLZSi2U","115Q",""]}"#,
+    );
+    check_error_deserialization::<SomeBytes>(
+        r#"{"bytes":["2NEpo0"]}"#,
+        r#"Invalid base58 string: invalid character '0' at offset 5 at line 1 column 19"#,
+    );
+}
+
+#[test]
+fn base58_check() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    pub struct SomeBytes {
+        #[serde(with = "As::<Base58Check>")]
+        vec: Vec<u8>,
+        #[serde(with = "As::<Base58Check>")]
+        array: [u8; 4],
+        #[serde(with = "As::<Base58Check>")]
+        empty: Box<[u8]>,
+    }
+    is_equal(
+        SomeBytes {
+            vec: vec![
+                0x00, 0x01, 0x09, 0x66, 0x77, 0x60, 0x06, 0x95, 0x3d, 0x55, 0x67, 0x43, 0x9e, 0x5e,
+                0x39, 0xf8, 0x6a, 0x0d, 0x27, 0x3b, 0xee,
+            ],
+            array: [5, 1, 2, 3],
+            empty: Box::new([]),
+        },
+        r#"{"vec":"16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM","array":"qYaZ76f7do","empty":"3QJmnh"}"#,
+    );
+    check_error_deserialization::<SomeBytes>(
+        r#"{"vec":"16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvN","array":"","empty":""}"#,
+        r#"Invalid Base58Check string: checksum mismatch at line 1 column 42"#,
+    );
+    check_error_deserialization::<SomeBytes>(
+        r#"{"vec":"2NE","array":"","empty":""}"#,
+        r#"Invalid Base58Check string: too short to contain a checksum at line 1 column 12"#,
+    );
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "base64")]
+
+mod utils;
+
+use crate::utils::{check_deserialization, check_error_deserialization, is_equal};
+use serde::{Deserialize, Serialize};
+use serde_with::{
+    base64::{Base64, Unpadded, UrlSafe},
+    As,
+};
+
+#[test]
+fn base64_vec() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    pub struct SomeBytes {
+        #[serde(with = "As::<Vec<Base64>>")]
+        bytes: Vec<Vec<u8>>,
+    }
+    is_equal(
+        SomeBytes {
+            bytes: vec![vec![0, 1, 2, 13], vec![0xfb, 0xff], vec![]],
+        },
+        r#"{"bytes":["AAECDQ==","+/8=",""]}"#,
+    );
+
+    // Missing padding is accepted
+    check_deserialization(
+        SomeBytes {
+            bytes: vec![vec![0, 1, 2, 13]],
+        },
+        r#"{"bytes":["AAECDQ"]}"#,
+    );
+    check_error_deserialization::<SomeBytes>(
+        r#"{"bytes":["AAE*DQ=="]}"#,
+        r#"Invalid base64 string: invalid character '*' at offset 3 at line 1 column 21"#,
+    );
+    check_error_deserialization::<SomeBytes>(
+        r#"{"bytes":["AAECD"]}"#,
+        r#"Invalid base64 string: invalid length at line 1 column 18"#,
+    );
+}
+
+#[test]
+fn base64_url_safe_unpadded() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    pub struct SomeBytes {
+        #[serde(with = "As::<Base64<UrlSafe, Unpadded>>")]
+        vec: Vec<u8>,
+        #[serde(with = "As::<Base64<UrlSafe, Unpadded>>")]
+        array: [u8; 4],
+        #[serde(with = "As::<Base64<UrlSafe, Unpadded>>")]
+        boxed: Box<[u8]>,
+    }
+    is_equal(
+        SomeBytes {
+            vec: vec![0xfb, 0xff],
+            array: [0, 1, 2, 13],
+            boxed: vec![0xff, 0xfe, 0xfd].into_boxed_slice(),
+        },
+        r#"{"vec":"-_8","array":"AAECDQ","boxed":"__79"}"#,
+    );
+    check_error_deserialization::<SomeBytes>(
+        r#"{"vec":"+/8","array":"AAECDQ","boxed":""}"#,
+        r#"Invalid base64 string: invalid character '+' at offset 0 at line 1 column 12"#,
+    );
+    check_error_deserialization::<SomeBytes>(
+        r#"{"vec":"","array":"AAEC","boxed":""}"#,
+        r#"Can't convert a byte vector of length 3 to the output type. at line 1 column 24"#,
+    );
+}
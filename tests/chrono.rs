@@ -2,12 +2,18 @@
 
 mod utils;
 
-use crate::utils::is_equal;
-use chrono_crate::{DateTime, NaiveDateTime, Utc};
+use crate::utils::{
+    check_deserialization, check_error_deserialization, check_serialization, is_equal,
+};
+use chrono_crate::{DateTime, FixedOffset, Local, NaiveDateTime, Utc};
 use pretty_assertions::assert_eq;
 use serde::Serialize;
 use serde_derive::Deserialize;
-use serde_with::{As, SameAs};
+use serde_with::{
+    As, DayDesignators, DurationHumanString, DurationMilliSeconds, DurationSeconds,
+    DurationSecondsWithFrac, Flexible, Iso8601Duration, SameAs, TimestampMilliSeconds,
+    TimestampMilliSecondsWithFrac, TimestampSeconds, TimestampSecondsWithFrac,
+};
 use std::{collections::BTreeMap, str::FromStr};
 
 fn new_datetime(secs: i64, nsecs: u32) -> DateTime<Utc> {
@@ -115,3 +121,396 @@ fn test_chrono_btree_map_naive_date_time() {
         r#"{"stamps":{"1":"1994-11-05T08:15:30Z","2":"1994-11-05T08:15:31Z"}}"#,
     );
 }
+
+#[test]
+fn test_chrono_timestamp_seconds() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructIntStrict {
+        #[serde(with = "As::<TimestampSeconds>")]
+        utc: DateTime<Utc>,
+        #[serde(with = "As::<TimestampSeconds>")]
+        fixed: DateTime<FixedOffset>,
+        #[serde(with = "As::<TimestampSeconds>")]
+        local: DateTime<Local>,
+        #[serde(with = "As::<TimestampSeconds>")]
+        naive: NaiveDateTime,
+    }
+
+    let datetime = new_datetime(1_478_563_200, 0);
+    is_equal(
+        StructIntStrict {
+            utc: datetime,
+            fixed: datetime.into(),
+            local: datetime.into(),
+            naive: datetime.naive_utc(),
+        },
+        r#"{"utc":1478563200,"fixed":1478563200,"local":1478563200,"naive":1478563200}"#,
+    );
+    let datetime = new_datetime(-86_000, 0);
+    is_equal(
+        StructIntStrict {
+            utc: datetime,
+            fixed: datetime.into(),
+            local: datetime.into(),
+            naive: datetime.naive_utc(),
+        },
+        r#"{"utc":-86000,"fixed":-86000,"local":-86000,"naive":-86000}"#,
+    );
+    check_error_deserialization::<StructIntStrict>(
+        r#"{"utc":"1478563200","fixed":0,"local":0,"naive":0}"#,
+        r#"invalid type: string "1478563200", expected i64 at line 1 column 19"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructFlexible {
+        #[serde(with = "As::<TimestampSecondsWithFrac<String, Flexible>>")]
+        value: DateTime<Utc>,
+    }
+
+    is_equal(
+        StructFlexible {
+            value: new_datetime(1_478_563_200, 123_000_000),
+        },
+        r#"{"value":"1478563200.123"}"#,
+    );
+    check_deserialization(
+        StructFlexible {
+            value: new_datetime(1_478_563_200, 0),
+        },
+        r#"{"value":1478563200}"#,
+    );
+    check_deserialization(
+        StructFlexible {
+            value: new_datetime(-86_001, 1_000_000),
+        },
+        r#"{"value":"-86000.999"}"#,
+    );
+    check_deserialization(
+        StructFlexible {
+            value: new_datetime(-2, 500_000_000),
+        },
+        r#"{"value":-1.5}"#,
+    );
+    check_error_deserialization::<StructFlexible>(
+        r#"{"value":"0.1234567890"}"#,
+        r#"TimestampSecondsWithFrac only supports nanosecond precision but '0.1234567890' has more than 9 fractional digits. at line 1 column 23"#,
+    );
+    check_error_deserialization::<StructFlexible>(
+        r#"{"value":"a"}"#,
+        r#"invalid value: string "a", expected an integer, a float, or a string containing a number at line 1 column 12"#,
+    );
+    check_error_deserialization::<StructFlexible>(
+        r#"{"value":9223372036854775807}"#,
+        r#"Invalid or out of range value '9223372036854775807' for DateTime at line 1 column 29"#,
+    );
+}
+
+#[test]
+fn test_chrono_timestamp_milli_seconds() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructIntStrict {
+        #[serde(with = "As::<TimestampMilliSeconds>")]
+        value: DateTime<Utc>,
+    }
+
+    is_equal(
+        StructIntStrict {
+            value: new_datetime(1_478_563_200, 123_000_000),
+        },
+        r#"{"value":1478563200123}"#,
+    );
+    is_equal(
+        StructIntStrict {
+            value: new_datetime(-1, 999_000_000),
+        },
+        r#"{"value":-1}"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Structf64Strict {
+        #[serde(with = "As::<TimestampMilliSecondsWithFrac>")]
+        value: NaiveDateTime,
+    }
+
+    is_equal(
+        Structf64Strict {
+            value: new_datetime(0, 1_500_000).naive_utc(),
+        },
+        r#"{"value":1.5}"#,
+    );
+}
+
+#[test]
+fn test_chrono_duration_seconds() {
+    use chrono_crate::Duration;
+    let one_second = Duration::seconds(1);
+    let minus_one_and_half = -Duration::milliseconds(1_500);
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructIntStrict {
+        #[serde(with = "As::<DurationSeconds>")]
+        value: Duration,
+    }
+
+    is_equal(StructIntStrict { value: one_second }, r#"{"value":1}"#);
+    is_equal(StructIntStrict { value: -one_second }, r#"{"value":-1}"#);
+    check_serialization(
+        StructIntStrict {
+            value: minus_one_and_half,
+        },
+        r#"{"value":-2}"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructStringStrict {
+        #[serde(with = "As::<DurationSecondsWithFrac<String>>")]
+        value: Duration,
+    }
+
+    is_equal(
+        StructStringStrict {
+            value: minus_one_and_half,
+        },
+        r#"{"value":"-1.5"}"#,
+    );
+    is_equal(
+        StructStringStrict {
+            value: -Duration::nanoseconds(1),
+        },
+        r#"{"value":"-0.000000001"}"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Structf64Flexible {
+        #[serde(with = "As::<DurationSecondsWithFrac<f64, Flexible>>")]
+        value: Duration,
+    }
+
+    is_equal(
+        Structf64Flexible {
+            value: minus_one_and_half,
+        },
+        r#"{"value":-1.5}"#,
+    );
+    check_deserialization(
+        Structf64Flexible {
+            value: minus_one_and_half,
+        },
+        r#"{"value":"-1.5"}"#,
+    );
+    check_deserialization(Structf64Flexible { value: -one_second }, r#"{"value":-1}"#);
+    check_error_deserialization::<Structf64Flexible>(
+        r#"{"value":18446744073709551615}"#,
+        r#"Invalid or out of range value '18446744073709551615' for chrono::Duration at line 1 column 30"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructMillis {
+        #[serde(with = "As::<DurationMilliSeconds<String, Flexible>>")]
+        value: Duration,
+    }
+
+    is_equal(
+        StructMillis {
+            value: minus_one_and_half,
+        },
+        r#"{"value":"-1500"}"#,
+    );
+    check_deserialization(
+        StructMillis {
+            value: -Duration::microseconds(1_500),
+        },
+        r#"{"value":"-1.5"}"#,
+    );
+}
+
+#[test]
+fn test_chrono_iso8601_duration() {
+    use chrono_crate::Duration;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Struct {
+        #[serde(with = "As::<Iso8601Duration<DayDesignators>>")]
+        value: Duration,
+    }
+
+    is_equal(
+        Struct {
+            value: Duration::milliseconds(5_405_250),
+        },
+        r#"{"value":"PT1H30M5.25S"}"#,
+    );
+    is_equal(
+        Struct {
+            value: -Duration::milliseconds(1_500),
+        },
+        r#"{"value":"-PT1.5S"}"#,
+    );
+    check_deserialization(
+        Struct {
+            value: -Duration::hours(76),
+        },
+        r#"{"value":"-P3DT4H"}"#,
+    );
+}
+
+#[test]
+fn test_chrono_duration_human_string() {
+    use chrono_crate::Duration;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Struct {
+        #[serde(with = "As::<DurationHumanString>")]
+        value: Duration,
+    }
+
+    is_equal(
+        Struct {
+            value: -Duration::milliseconds(90_250),
+        },
+        r#"{"value":"-90.25s"}"#,
+    );
+    check_deserialization(
+        Struct {
+            value: -Duration::seconds(5400),
+        },
+        r#"{"value":"-1h 30m"}"#,
+    );
+}
+
+#[test]
+fn test_chrono_formatted_date_time() {
+    use chrono_crate::{NaiveDate, NaiveTime, TimeZone};
+    use serde_with::chrono::{
+        DateTimeFormat, FormattedDateTime, Rfc2822, Rfc3339MicroSeconds, Rfc3339MilliSeconds,
+        Rfc3339Seconds,
+    };
+
+    struct Partner;
+    impl DateTimeFormat for Partner {
+        fn format() -> &'static str {
+            "%d/%m/%Y %H:%M"
+        }
+    }
+
+    struct Compact;
+    impl DateTimeFormat for Compact {
+        fn format() -> &'static str {
+            "%Y%m%dT%H%M%S"
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructNaive {
+        #[serde(with = "As::<FormattedDateTime<Partner>>")]
+        datetime: NaiveDateTime,
+        #[serde(with = "As::<FormattedDateTime<Compact>>")]
+        compact: NaiveDateTime,
+    }
+
+    let datetime = NaiveDate::from_ymd_opt(2019, 12, 24)
+        .and_then(|date| date.and_hms_opt(18, 30, 0))
+        .unwrap();
+    is_equal(
+        StructNaive {
+            datetime,
+            compact: datetime,
+        },
+        r#"{"datetime":"24/12/2019 18:30","compact":"20191224T183000"}"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructDateTime {
+        #[serde(with = "As::<FormattedDateTime<Rfc3339Seconds>>")]
+        seconds: DateTime<Utc>,
+        #[serde(with = "As::<FormattedDateTime<Rfc3339MilliSeconds>>")]
+        millis: DateTime<FixedOffset>,
+        #[serde(with = "As::<FormattedDateTime<Rfc3339MicroSeconds>>")]
+        micros: DateTime<Utc>,
+        #[serde(with = "As::<FormattedDateTime<Rfc2822>>")]
+        rfc2822: DateTime<FixedOffset>,
+    }
+
+    let naive = |h, m, s, nano| {
+        NaiveDate::from_ymd_opt(2020, 1, 2)
+            .and_then(|date| date.and_hms_nano_opt(h, m, s, nano))
+            .unwrap()
+    };
+    let offset = FixedOffset::east_opt(3600).unwrap();
+    is_equal(
+        StructDateTime {
+            seconds: Utc.from_utc_datetime(&naive(3, 4, 5, 0)),
+            millis: offset.from_utc_datetime(&naive(3, 4, 5, 678_000_000)),
+            micros: Utc.from_utc_datetime(&naive(3, 4, 5, 678_901_000)),
+            rfc2822: offset.from_utc_datetime(&naive(3, 4, 5, 0)),
+        },
+        r#"{"seconds":"2020-01-02T03:04:05Z","millis":"2020-01-02T04:04:05.678+01:00","micros":"2020-01-02T03:04:05.678901Z","rfc2822":"Thu, 02 Jan 2020 04:04:05 +0100"}"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructUtc {
+        #[serde(with = "As::<FormattedDateTime<Rfc3339Seconds>>")]
+        seconds: DateTime<Utc>,
+        #[serde(with = "As::<FormattedDateTime<Rfc3339MilliSeconds>>")]
+        millis: DateTime<Utc>,
+        #[serde(with = "As::<FormattedDateTime<Rfc3339MicroSeconds>>")]
+        micros: DateTime<Utc>,
+    }
+
+    let utc = StructUtc {
+        seconds: Utc.from_utc_datetime(&naive(3, 4, 5, 0)),
+        millis: Utc.from_utc_datetime(&naive(3, 4, 5, 678_000_000)),
+        micros: Utc.from_utc_datetime(&naive(3, 4, 5, 678_901_000)),
+    };
+    is_equal(
+        utc,
+        r#"{"seconds":"2020-01-02T03:04:05Z","millis":"2020-01-02T03:04:05.678Z","micros":"2020-01-02T03:04:05.678901Z"}"#,
+    );
+    // Numeric offsets and a lowercase `z` are valid RFC 3339 too
+    check_deserialization(
+        StructUtc {
+            seconds: Utc.from_utc_datetime(&naive(3, 4, 5, 0)),
+            millis: Utc.from_utc_datetime(&naive(3, 4, 5, 678_000_000)),
+            micros: Utc.from_utc_datetime(&naive(3, 4, 5, 678_901_000)),
+        },
+        r#"{"seconds":"2020-01-02T03:04:05+00:00","millis":"2020-01-02T04:04:05.678+01:00","micros":"2020-01-02T03:04:05.678901z"}"#,
+    );
+    check_error_deserialization::<StructUtc>(
+        r#"{"seconds":"2020-01-02 03:04:05","millis":"","micros":""}"#,
+        r#"Failed to parse '2020-01-02 03:04:05' with format 'RFC 3339': premature end of input at line 1 column 32"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructDateAndTime {
+        #[serde(with = "As::<FormattedDateTime<Partner>>")]
+        date: NaiveDate,
+        #[serde(with = "As::<FormattedDateTime<Compact>>")]
+        time: NaiveTime,
+    }
+
+    check_deserialization(
+        StructDateAndTime {
+            date: datetime.date(),
+            time: datetime.time(),
+        },
+        r#"{"date":"24/12/2019 18:30","time":"20191224T183000"}"#,
+    );
+
+    check_error_deserialization::<StructNaive>(
+        r#"{"datetime":"2019-12-24 18:30","compact":"20191224T183000"}"#,
+        r#"Failed to parse '2019-12-24 18:30' with format '%d/%m/%Y %H:%M': input contains invalid characters at line 1 column 30"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructInvalidFormat {
+        #[serde(with = "As::<FormattedDateTime<Rfc2822>>")]
+        value: NaiveDateTime,
+    }
+
+    assert_eq!(
+        serde_json::to_string(&StructInvalidFormat { value: datetime })
+            .unwrap_err()
+            .to_string(),
+        "Failed to format the value with format '%a, %d %b %Y %H:%M:%S %z'"
+    );
+}
@@ -2,9 +2,13 @@
 
 mod utils;
 
-use crate::utils::is_equal;
+use crate::utils::{check_deserialization, check_error_deserialization, is_equal};
 use serde::{Deserialize, Serialize};
-use serde_with::{hex::Hex, As};
+use serde_with::{
+    hex::{Hex, Lowercase, Prefixed, Uppercase},
+    As,
+};
+use std::borrow::Cow;
 
 #[test]
 fn hex_vec() {
@@ -19,4 +23,82 @@ fn hex_vec() {
         },
         r#"{"bytes":["0001020d","0e050607"]}"#,
     );
+
+    // Uppercase letters are accepted too
+    check_deserialization(
+        SomeBytes {
+            bytes: vec![vec![0xde, 0xad, 0xbe, 0xef]],
+        },
+        r#"{"bytes":["DEADBEEF"]}"#,
+    );
+    check_error_deserialization::<SomeBytes>(
+        r#"{"bytes":["0"]}"#,
+        r#"Invalid hex string: odd number of digits at line 1 column 14"#,
+    );
+    check_error_deserialization::<SomeBytes>(
+        r#"{"bytes":["zz"]}"#,
+        r#"Invalid hex string: invalid character 'z' at offset 0 at line 1 column 15"#,
+    );
+}
+
+#[test]
+fn hex_other_containers() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    pub struct SomeBytes {
+        #[serde(with = "As::<Hex>")]
+        array: [u8; 4],
+        #[serde(with = "As::<Hex>")]
+        boxed: Box<[u8]>,
+        #[serde(with = "As::<Hex>")]
+        cow: Cow<'static, [u8]>,
+    }
+    is_equal(
+        SomeBytes {
+            array: [1, 2, 3, 4],
+            boxed: vec![0xab].into_boxed_slice(),
+            cow: Cow::Borrowed(&[0xcd, 0xef]),
+        },
+        r#"{"array":"01020304","boxed":"ab","cow":"cdef"}"#,
+    );
+    check_error_deserialization::<SomeBytes>(
+        r#"{"array":"010203","boxed":"","cow":""}"#,
+        r#"Can't convert a byte vector of length 3 to the output type. at line 1 column 17"#,
+    );
+}
+
+#[test]
+fn hex_uppercase_and_prefixed() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    pub struct SomeBytes {
+        #[serde(with = "As::<Hex<Uppercase>>")]
+        upper: Vec<u8>,
+        #[serde(with = "As::<Hex<Lowercase, Prefixed>>")]
+        prefixed: [u8; 2],
+        #[serde(with = "As::<Hex<Uppercase, Prefixed>>")]
+        upper_prefixed: Vec<u8>,
+    }
+    is_equal(
+        SomeBytes {
+            upper: vec![0xde, 0xad, 0xbe, 0xef],
+            prefixed: [0x0b, 0xad],
+            upper_prefixed: vec![0xca, 0xfe],
+        },
+        r#"{"upper":"DEADBEEF","prefixed":"0x0bad","upper_prefixed":"0xCAFE"}"#,
+    );
+    check_deserialization(
+        SomeBytes {
+            upper: vec![0xde, 0xad],
+            prefixed: [0xca, 0xfe],
+            upper_prefixed: vec![0xbe, 0xef],
+        },
+        r#"{"upper":"dead","prefixed":"0xCAFE","upper_prefixed":"0Xbeef"}"#,
+    );
+    check_error_deserialization::<SomeBytes>(
+        r#"{"upper":"","prefixed":"cafe","upper_prefixed":""}"#,
+        r#"Invalid hex string: expected the prefix '0x' at line 1 column 29"#,
+    );
+    check_error_deserialization::<SomeBytes>(
+        r#"{"upper":"","prefixed":"0xcage","upper_prefixed":""}"#,
+        r#"Invalid hex string: invalid character 'g' at offset 4 at line 1 column 31"#,
+    );
 }
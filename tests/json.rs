@@ -2,9 +2,13 @@
 
 mod utils;
 
-use crate::utils::is_equal;
+use crate::utils::{check_error_deserialization, is_equal};
 use serde::{Deserialize, Serialize};
-use serde_with::{json::JsonString, As, DisplayFromStr};
+use serde_with::{
+    json::{JsonString, MapKeysAsJsonString},
+    As, DisplayFromStr,
+};
+use std::collections::{BTreeMap, HashMap};
 
 #[test]
 fn test_nested_json() {
@@ -27,3 +31,47 @@ fn test_nested_json() {
         r#"{"value":"{\"value\":\"444\"}"}"#,
     );
 }
+
+#[test]
+fn test_map_keys_as_json_string() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct Key {
+        id: u32,
+        name: String,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Struct {
+        #[serde(with = "As::<MapKeysAsJsonString>")]
+        tuples: BTreeMap<(u32, u32), u32>,
+        #[serde(with = "As::<MapKeysAsJsonString<DisplayFromStr>>")]
+        structs: HashMap<Key, u32>,
+        #[serde(with = "As::<MapKeysAsJsonString>")]
+        strings: BTreeMap<String, u32>,
+    };
+
+    is_equal(
+        Struct {
+            tuples: vec![((1, 2), 3), ((4, 5), 6)].into_iter().collect(),
+            structs: vec![(
+                Key {
+                    id: 1,
+                    name: "a".into(),
+                },
+                2,
+            )]
+            .into_iter()
+            .collect(),
+            strings: vec![("a".into(), 1)].into_iter().collect(),
+        },
+        r#"{"tuples":{"[1,2]":3,"[4,5]":6},"structs":{"{\"id\":1,\"name\":\"a\"}":"2"},"strings":{"\"a\"":1}}"#,
+    );
+    check_error_deserialization::<Struct>(
+        r#"{"tuples":{"[1,2,3]":3},"structs":{},"strings":{}}"#,
+        r#"trailing characters at line 1 column 6"#,
+    );
+    check_error_deserialization::<Struct>(
+        r#"{"tuples":{},"structs":{},"strings":{"a":1}}"#,
+        r#"expected value at line 1 column 1"#,
+    );
+}
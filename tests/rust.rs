@@ -162,6 +162,101 @@ fn prohibit_duplicate_value_btreemap() {
     assert!(res.is_err());
 }
 
+#[test]
+fn report_all_duplicate_keys_hashmap() {
+    use std::collections::HashMap;
+    #[derive(Debug, Eq, PartialEq, Deserialize)]
+    struct Doc {
+        #[serde(with = "::serde_with::rust::maps_report_all_duplicate_keys")]
+        map: HashMap<usize, usize>,
+    }
+
+    // Different value and key always works
+    let s = r#"{"map": {"1": 1, "2": 1, "3": 1}}"#;
+    let mut v = Doc {
+        map: HashMap::new(),
+    };
+    v.map.insert(1, 1);
+    v.map.insert(2, 1);
+    v.map.insert(3, 1);
+    assert_eq!(v, serde_json::from_str(s).unwrap());
+
+    // All duplicate keys are reported
+    let s = r#"{"map": {"1": 1, "2": 2, "1": 3, "3": 4, "2": 5, "1": 6}}"#;
+    let res: Result<Doc, _> = serde_json::from_str(s);
+    assert_eq!(
+        "invalid entries: found 2 duplicate keys: 1 with 3 values: 1, 3, 6; 2 with 2 values: 2, 5 at line 1 column 56",
+        res.unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn report_all_duplicate_keys_btreemap() {
+    use std::collections::BTreeMap;
+    #[derive(Debug, Eq, PartialEq, Deserialize)]
+    struct Doc {
+        #[serde(with = "::serde_with::rust::maps_report_all_duplicate_keys")]
+        map: BTreeMap<String, usize>,
+    }
+
+    // Different value and key always works
+    let s = r#"{"map": {"a": 1, "b": 2}}"#;
+    let mut v = Doc {
+        map: BTreeMap::new(),
+    };
+    v.map.insert("a".into(), 1);
+    v.map.insert("b".into(), 2);
+    assert_eq!(v, serde_json::from_str(s).unwrap());
+
+    // A single duplicate key
+    let s = r#"{"map": {"a": 1, "b": 2, "a": 3}}"#;
+    let res: Result<Doc, _> = serde_json::from_str(s);
+    assert_eq!(
+        r#"invalid entries: found 1 duplicate key: "a" with 2 values: 1, 3 at line 1 column 32"#,
+        res.unwrap_err().to_string()
+    );
+
+    // Other errors are reported as usual
+    let s = r#"{"map": {"a": 1, "a": "b"}}"#;
+    let res: Result<Doc, _> = serde_json::from_str(s);
+    assert_eq!(
+        r#"invalid type: string "b", expected usize at line 1 column 25"#,
+        res.unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn report_all_duplicate_keys_same_debug_output() {
+    use std::{collections::BTreeMap, fmt};
+
+    // All keys share the same `Debug` output, but are still reported separately
+    #[derive(Eq, PartialEq, Ord, PartialOrd, Deserialize)]
+    struct Key(u8);
+
+    impl fmt::Debug for Key {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("Key")
+        }
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Doc {
+        #[serde(with = "::serde_with::rust::maps_report_all_duplicate_keys")]
+        map: BTreeMap<Key, usize>,
+    }
+
+    let s = r#"{"map": {"1": 1, "2": 2}}"#;
+    let doc: Doc = serde_json::from_str(s).unwrap();
+    assert_eq!(2, doc.map.len());
+
+    let s = r#"{"map": {"1": 1, "2": 2, "1": 3, "2": 4}}"#;
+    let res: Result<Doc, _> = serde_json::from_str(s);
+    assert_eq!(
+        "invalid entries: found 2 duplicate keys: Key with 2 values: 1, 3; Key with 2 values: 2, 4 at line 1 column 40",
+        res.unwrap_err().to_string()
+    );
+}
+
 #[test]
 fn duplicate_key_first_wins_hashmap() {
     use std::collections::HashMap;
@@ -3,15 +3,35 @@ mod utils;
 use crate::utils::{
     check_deserialization, check_error_deserialization, check_serialization, is_equal,
 };
-use serde::{Deserialize, Serialize};
+use serde::{
+    de::{Error, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
 use serde_with::{
-    As, BytesOrString, DefaultOnError, DisplayFromStr, DurationSeconds, DurationSecondsWithFrac,
-    Flexible, Integer, NoneAsEmptyString, Same, SameAs,
+    duplicate_key_impls::{DuplicateInsertsFirstWinsMap, MapEntry},
+    sentinel::{EmptyString, NotAvailable, Zero},
+    As, Bytes, BytesOrString, DayDesignators, DefaultOnError, DisplayFromStr, DurationHumanString,
+    DurationMicroSeconds, DurationMicroSecondsWithFrac, DurationMilliSeconds,
+    DurationMilliSecondsWithFrac, DurationNanoSeconds, DurationNanoSecondsWithFrac,
+    DurationSeconds, DurationSecondsWithFrac, Flexible, FromInto, Integer, Iso8601Duration,
+    KeyValueMap, MapFirstKeyWins, MapKeysDisplayFromStr, MapPreventDuplicates, MapSkipError,
+    NoneAs, NoneAsEmptyString, OneOrMany, PickFirst, PreferMany, Same, SameAs, Sentinel,
+    SentinelValue, SetLastValueWins, SetPreventDuplicates, SkipErrorHook,
+    TimestampMilliSecondsWithFrac, TimestampNanoSeconds, TimestampNanoSecondsWithFrac,
+    TimestampSeconds, TimestampSecondsWithFrac, TryFromInto, VecSkipError,
 };
 use std::{
-    collections::{BTreeMap, HashMap, LinkedList, VecDeque},
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque},
+    convert::TryFrom,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    net::Ipv4Addr,
     rc::Rc,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
 };
 
 #[test]
@@ -280,6 +300,37 @@ fn test_tuple_list_as_map() {
     is_equal(StructOption { values: None }, r#"{"values":{}}"#);
 }
 
+#[test]
+fn test_map_keys_display_fromstr() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Struct {
+        #[serde(with = "As::<MapKeysDisplayFromStr>")]
+        btree: BTreeMap<u32, String>,
+        #[serde(with = "As::<MapKeysDisplayFromStr<DisplayFromStr>>")]
+        hash: HashMap<Ipv4Addr, bool>,
+    };
+
+    is_equal(
+        Struct {
+            btree: vec![(1, "a".into()), (20, "b".into())]
+                .into_iter()
+                .collect(),
+            hash: vec![(Ipv4Addr::new(127, 0, 0, 1), true)]
+                .into_iter()
+                .collect(),
+        },
+        r#"{"btree":{"1":"a","20":"b"},"hash":{"127.0.0.1":"true"}}"#,
+    );
+    check_error_deserialization::<Struct>(
+        r#"{"btree":{"x":"a"},"hash":{}}"#,
+        r#"invalid digit found in string at line 1 column 13"#,
+    );
+    check_error_deserialization::<Struct>(
+        r#"{"btree":{},"hash":{"127.0.0":"true"}}"#,
+        r#"invalid IPv4 address syntax at line 1 column 29"#,
+    );
+}
+
 #[test]
 fn test_none_as_empty_string() {
     #[derive(Debug, Serialize, Deserialize, PartialEq)]
@@ -337,6 +388,102 @@ fn test_none_as_empty_string() {
         },
         r#"{"value":"Hello"}"#,
     );
+
+    // A format which is not self-describing and only supports `deserialize_str`
+    struct StrOnly(&'static str);
+
+    impl<'de> Deserializer<'de> for StrOnly {
+        type Error = serde::de::value::Error;
+
+        fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+        where
+            V: Visitor<'de>,
+        {
+            Err(Error::custom("deserialize_any is not supported"))
+        }
+
+        fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where
+            V: Visitor<'de>,
+        {
+            visitor.visit_str(self.0)
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map struct enum identifier ignored_any
+        }
+    }
+
+    let value: Option<String> = As::<NoneAsEmptyString>::deserialize(StrOnly("")).unwrap();
+    assert_eq!(None, value);
+    let value: Option<String> = As::<NoneAsEmptyString>::deserialize(StrOnly("Hello")).unwrap();
+    assert_eq!(Some("Hello".to_string()), value);
+}
+
+#[test]
+fn test_none_as() {
+    struct Missing;
+
+    impl Sentinel for Missing {
+        fn sentinels() -> &'static [SentinelValue] {
+            &[
+                SentinelValue::Str("N/A"),
+                SentinelValue::Str("-"),
+                SentinelValue::Str("null"),
+                SentinelValue::Int(0),
+            ]
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Struct {
+        #[serde(with = "As::<NoneAs<NotAvailable, DisplayFromStr>>")]
+        count: Option<u32>,
+        #[serde(with = "As::<Vec<NoneAs<Missing>>>")]
+        values: Vec<Option<i32>>,
+        #[serde(with = "As::<NoneAs<Zero>>")]
+        id: Option<u64>,
+        #[serde(with = "As::<NoneAs<EmptyString>>")]
+        name: Option<String>,
+    };
+
+    is_equal(
+        Struct {
+            count: None,
+            values: vec![Some(-1), None],
+            id: None,
+            name: None,
+        },
+        r#"{"count":"N/A","values":[-1,"N/A"],"id":0,"name":""}"#,
+    );
+    is_equal(
+        Struct {
+            count: Some(5),
+            values: vec![],
+            id: Some(42),
+            name: Some("Hello".into()),
+        },
+        r#"{"count":"5","values":[],"id":42,"name":"Hello"}"#,
+    );
+    check_deserialization(
+        Struct {
+            count: None,
+            values: vec![None, None, None, None, Some(1)],
+            id: None,
+            name: Some("N/A".into()),
+        },
+        r#"{"count":"N/A","values":["N/A","-","null",0,1],"id":0,"name":"N/A"}"#,
+    );
+    check_error_deserialization::<Struct>(
+        r#"{"count":"n/a","values":[],"id":0,"name":""}"#,
+        r#"invalid digit found in string at line 1 column 14"#,
+    );
+    check_error_deserialization::<Struct>(
+        r#"{"count":"1","values":["none"],"id":0,"name":""}"#,
+        r#"invalid type: string "none", expected i32 at line 1 column 30"#,
+    );
 }
 
 #[test]
@@ -401,6 +548,232 @@ fn test_default_on_error() {
     check_deserialization(Struct3 { value: vec![0, 0] }, r#"{"value":["AA",5]}"#);
 }
 
+#[test]
+fn test_vec_skip_error() {
+    // One bit for every skipped index
+    static SKIPPED: AtomicUsize = AtomicUsize::new(0);
+
+    struct CollectSkipped;
+
+    impl SkipErrorHook for CollectSkipped {
+        fn skipped(index: usize, _error: &dyn Display) {
+            SKIPPED.fetch_or(1 << index, Ordering::SeqCst);
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Struct {
+        #[serde(with = "As::<VecSkipError<Same>>")]
+        values: Vec<u8>,
+        #[serde(with = "As::<VecSkipError<DisplayFromStr, CollectSkipped>>")]
+        parsed: Vec<u32>,
+    };
+
+    is_equal(
+        Struct {
+            values: vec![1, 2],
+            parsed: vec![3],
+        },
+        r#"{"values":[1,2],"parsed":["3"]}"#,
+    );
+    check_deserialization(
+        Struct {
+            values: vec![1, 3],
+            parsed: vec![4, 6],
+        },
+        r#"{"values":[1,256,3,"4",null,[5]],"parsed":["4","x",6,"6",{"7":7}]}"#,
+    );
+    assert_eq!(0b10110, SKIPPED.load(Ordering::SeqCst));
+
+    // Errors of the sequence itself are not skipped
+    check_error_deserialization::<Struct>(
+        r#"{"values":{},"parsed":[]}"#,
+        r#"invalid type: map, expected a sequence at line 1 column 10"#,
+    );
+    check_error_deserialization::<Struct>(
+        r#"{"values":[1,2,],"parsed":[]}"#,
+        r#"trailing comma at line 1 column 16"#,
+    );
+}
+
+#[test]
+fn test_map_skip_error() {
+    static SKIPPED: AtomicUsize = AtomicUsize::new(0);
+
+    struct CountSkipped;
+
+    impl SkipErrorHook for CountSkipped {
+        fn skipped(_index: usize, _error: &dyn Display) {
+            SKIPPED.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Struct {
+        #[serde(with = "As::<MapSkipError<DisplayFromStr, Same>>")]
+        btree: BTreeMap<u8, u32>,
+        #[serde(with = "As::<MapSkipError<Same, DisplayFromStr, CountSkipped>>")]
+        hash: HashMap<String, bool>,
+    };
+
+    is_equal(
+        Struct {
+            btree: vec![(1, 10)].into_iter().collect(),
+            hash: vec![("a".to_string(), true)].into_iter().collect(),
+        },
+        r#"{"btree":{"1":10},"hash":{"a":"true"}}"#,
+    );
+    check_deserialization(
+        Struct {
+            btree: vec![(1, 10), (3, 30)].into_iter().collect(),
+            hash: vec![("a".to_string(), true)].into_iter().collect(),
+        },
+        r#"{"btree":{"1":10,"256":20,"3":30,"4":-1},"hash":{"a":"true","b":"yes"}}"#,
+    );
+    assert_eq!(1, SKIPPED.load(Ordering::SeqCst));
+
+    check_error_deserialization::<Struct>(
+        r#"{"btree":[],"hash":{}}"#,
+        r#"invalid type: sequence, expected a map at line 1 column 9"#,
+    );
+}
+
+#[test]
+fn test_map_duplicate_policies() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Struct {
+        #[serde(with = "As::<Vec<MapPreventDuplicates<DisplayFromStr, Same>>>")]
+        prevent: Vec<BTreeMap<u8, u32>>,
+        #[serde(with = "As::<MapFirstKeyWins<Same, DisplayFromStr>>")]
+        first: HashMap<String, bool>,
+    };
+
+    is_equal(
+        Struct {
+            prevent: vec![
+                vec![(1, 10)].into_iter().collect(),
+                vec![(1, 10), (2, 20)].into_iter().collect(),
+            ],
+            first: vec![("a".to_string(), true)].into_iter().collect(),
+        },
+        r#"{"prevent":[{"1":10},{"1":10,"2":20}],"first":{"a":"true"}}"#,
+    );
+    check_deserialization(
+        Struct {
+            prevent: vec![],
+            first: vec![("a".to_string(), true), ("b".to_string(), false)]
+                .into_iter()
+                .collect(),
+        },
+        r#"{"prevent":[],"first":{"a":"true","b":"false","a":"false"}}"#,
+    );
+
+    check_error_deserialization::<Struct>(
+        r#"{"prevent":[{"1":10},{"1":10,"1":20}],"first":{}}"#,
+        r#"invalid entry: found duplicate key at line 1 column 36"#,
+    );
+    check_error_deserialization::<Struct>(
+        r#"{"prevent":[{"x":10}],"first":{}}"#,
+        r#"invalid digit found in string at line 1 column 16"#,
+    );
+}
+
+#[test]
+fn test_set_duplicate_policies() {
+    // Equality only considers the first field, such that the kept value is observable
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Entry(u8, String);
+
+    impl PartialEq for Entry {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl Eq for Entry {}
+
+    impl Hash for Entry {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.0.hash(state);
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Struct {
+        #[serde(with = "As::<SetPreventDuplicates<DisplayFromStr>>")]
+        prevent: BTreeSet<u8>,
+        #[serde(with = "As::<SetLastValueWins<Same>>")]
+        last: HashSet<Entry>,
+    };
+
+    is_equal(
+        Struct {
+            prevent: vec![1, 2].into_iter().collect(),
+            last: vec![Entry(1, "a".into())].into_iter().collect(),
+        },
+        r#"{"prevent":["1","2"],"last":[[1,"a"]]}"#,
+    );
+    let s: Struct = serde_json::from_str(r#"{"prevent":[],"last":[[1,"a"],[1,"b"]]}"#).unwrap();
+    assert_eq!("b", s.last.iter().next().unwrap().1);
+
+    check_error_deserialization::<Struct>(
+        r#"{"prevent":["1","2","1"],"last":[]}"#,
+        r#"invalid entry: found duplicate value at line 1 column 24"#,
+    );
+}
+
+#[test]
+fn test_duplicate_policies_custom_collection() {
+    // Map which keeps the insertion order
+    #[derive(Debug, PartialEq)]
+    struct VecMap(Vec<(String, u32)>);
+
+    impl MapEntry for VecMap {
+        type Key = String;
+        type Value = u32;
+    }
+
+    impl DuplicateInsertsFirstWinsMap<String, u32> for VecMap {
+        fn new(size_hint: Option<usize>) -> Self {
+            VecMap(Vec::with_capacity(size_hint.unwrap_or(0)))
+        }
+
+        fn insert(&mut self, key: String, value: u32) {
+            if self.0.iter().all(|(k, _)| *k != key) {
+                self.0.push((key, value));
+            }
+        }
+    }
+
+    impl<'a> IntoIterator for &'a VecMap {
+        type Item = (&'a String, &'a u32);
+        type IntoIter = Box<dyn Iterator<Item = Self::Item> + 'a>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            Box::new(self.0.iter().map(|(k, v)| (k, v)))
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Struct {
+        #[serde(with = "As::<MapFirstKeyWins<Same, DisplayFromStr>>")]
+        map: VecMap,
+    };
+
+    is_equal(
+        Struct {
+            map: VecMap(vec![("b".into(), 2), ("a".into(), 1)]),
+        },
+        r#"{"map":{"b":"2","a":"1"}}"#,
+    );
+    check_deserialization(
+        Struct {
+            map: VecMap(vec![("b".into(), 2), ("a".into(), 1)]),
+        },
+        r#"{"map":{"b":"2","a":"1","b":"3"}}"#,
+    );
+}
+
 #[test]
 fn test_bytes_or_string() {
     #[derive(Debug, Serialize, Deserialize, PartialEq)]
@@ -447,133 +820,594 @@ fn test_bytes_or_string() {
 }
 
 #[test]
-fn test_duration_seconds() {
-    use std::time::Duration;
-    let zero = Duration::new(0, 0);
-    let one_second = Duration::new(1, 0);
-    let half_second = Duration::new(0, 500_000_000);
-
+fn test_bytes() {
     #[derive(Debug, Serialize, Deserialize, PartialEq)]
-    struct StructIntStrict {
-        #[serde(with = "As::<DurationSeconds>")]
-        value: Duration,
+    struct Struct {
+        #[serde(with = "As::<Bytes>")]
+        vec: Vec<u8>,
+        #[serde(with = "As::<Bytes>")]
+        array: [u8; 3],
+        #[serde(with = "As::<Bytes>")]
+        boxed: Box<[u8]>,
     };
 
-    is_equal(StructIntStrict { value: zero }, r#"{"value":0}"#);
-    is_equal(StructIntStrict { value: one_second }, r#"{"value":1}"#);
-    check_serialization(StructIntStrict { value: half_second }, r#"{"value":1}"#);
-    check_error_deserialization::<StructIntStrict>(
-        r#"{"value":"1"}"#,
-        r#"invalid type: string "1", expected u64 at line 1 column 12"#,
+    // JSON does not have a dedicated bytes type and serializes them as sequences
+    is_equal(
+        Struct {
+            vec: vec![1, 2, 3],
+            array: [4, 5, 6],
+            boxed: vec![7].into_boxed_slice(),
+        },
+        r#"{"vec":[1,2,3],"array":[4,5,6],"boxed":[7]}"#,
     );
-    check_error_deserialization::<StructIntStrict>(
-        r#"{"value":-1}"#,
-        r#"invalid value: integer `-1`, expected u64 at line 1 column 11"#,
+    check_deserialization(
+        Struct {
+            vec: b"Hello".to_vec(),
+            array: *b"abc",
+            boxed: Box::new([]),
+        },
+        r#"{"vec":"Hello","array":"abc","boxed":[]}"#,
+    );
+    check_error_deserialization::<Struct>(
+        r#"{"vec":[],"array":[1,2],"boxed":[]}"#,
+        r#"invalid length 2, expected an array of 3 bytes at line 1 column 23"#,
     );
 
     #[derive(Debug, Serialize, Deserialize, PartialEq)]
-    struct StructIntFlexible {
-        #[serde(with = "As::<DurationSeconds<Integer, Flexible>>")]
-        value: Duration,
+    struct StructBorrowed<'a> {
+        #[serde(borrow, with = "As::<Bytes>")]
+        slice: &'a [u8],
+        #[serde(borrow, with = "As::<Bytes>")]
+        cow: Cow<'a, [u8]>,
     };
 
-    is_equal(StructIntFlexible { value: zero }, r#"{"value":0}"#);
-    is_equal(StructIntFlexible { value: one_second }, r#"{"value":1}"#);
-    check_serialization(StructIntFlexible { value: half_second }, r#"{"value":1}"#);
-    check_deserialization(
-        StructIntFlexible { value: half_second },
-        r#"{"value":"0.5"}"#,
+    check_serialization(
+        StructBorrowed {
+            slice: &[8, 9],
+            cow: Cow::Borrowed(&[10]),
+        },
+        r#"{"slice":[8,9],"cow":[10]}"#,
     );
-    check_deserialization(StructIntFlexible { value: one_second }, r#"{"value":"1"}"#);
-    check_deserialization(StructIntFlexible { value: zero }, r#"{"value":"0"}"#);
-    check_error_deserialization::<StructIntFlexible>(
-        r#"{"value":"a"}"#,
-        r#"invalid value: string "a", expected an integer, a float, or a string containing a number at line 1 column 12"#,
+    let value: StructBorrowed<'_> =
+        serde_json::from_str(r#"{"slice":"borrowed","cow":"borrowed"}"#).unwrap();
+    assert_eq!(b"borrowed", value.slice);
+    match value.cow {
+        Cow::Borrowed(cow) => assert_eq!(b"borrowed", cow),
+        Cow::Owned(_) => panic!("Cow must borrow from the input"),
+    }
+    let value: StructBorrowed<'_> = serde_json::from_str(r#"{"slice":"","cow":[1]}"#).unwrap();
+    assert_eq!(Cow::<[u8]>::Owned(vec![1]), value.cow);
+    // Escaped strings cannot be borrowed
+    assert_eq!(
+        "invalid type: byte array, expected a borrowed byte array at line 1 column 13",
+        serde_json::from_str::<StructBorrowed<'_>>(r#"{"slice":"\n","cow":""}"#)
+            .unwrap_err()
+            .to_string(),
     );
-    check_error_deserialization::<StructIntFlexible>(
-        r#"{"value":-1}"#,
-        r#"Negative values are not supported for Duration. Found -1 at line 1 column 11"#,
+
+    // RON has a bytes type, which is written as base64
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructRon {
+        #[serde(with = "As::<Bytes>")]
+        value: Vec<u8>,
+    };
+    let ron = ron::ser::to_string(&StructRon {
+        value: vec![1, 2, 3],
+    })
+    .unwrap();
+    assert_eq!(r#"(value:"AQID",)"#, ron);
+    assert_eq!(
+        StructRon {
+            value: vec![1, 2, 3]
+        },
+        ron::de::from_str(&ron).unwrap()
     );
+}
+
+#[test]
+fn test_from_into() {
+    #[derive(Clone, Debug, PartialEq)]
+    struct Meters(f64);
+
+    impl From<f64> for Meters {
+        fn from(value: f64) -> Self {
+            Meters(value)
+        }
+    }
+
+    impl From<Meters> for f64 {
+        fn from(meters: Meters) -> Self {
+            meters.0
+        }
+    }
 
     #[derive(Debug, Serialize, Deserialize, PartialEq)]
-    struct Structf64Strict {
-        #[serde(with = "As::<DurationSeconds<f64>>")]
-        value: Duration,
+    struct Struct {
+        #[serde(with = "As::<FromInto<f64>>")]
+        value: Meters,
+        #[serde(with = "As::<Option<Vec<FromInto<f64>>>>")]
+        values: Option<Vec<Meters>>,
     };
 
-    is_equal(Structf64Strict { value: zero }, r#"{"value":0.0}"#);
-    is_equal(Structf64Strict { value: one_second }, r#"{"value":1.0}"#);
-    check_serialization(Structf64Strict { value: half_second }, r#"{"value":1.0}"#);
-    check_deserialization(Structf64Strict { value: half_second }, r#"{"value":0.5}"#);
-    check_error_deserialization::<Structf64Strict>(
-        r#"{"value":"1"}"#,
-        r#"invalid type: string "1", expected f64 at line 1 column 12"#,
+    is_equal(
+        Struct {
+            value: Meters(1.5),
+            values: Some(vec![Meters(2.0), Meters(-0.5)]),
+        },
+        r#"{"value":1.5,"values":[2.0,-0.5]}"#,
     );
-    check_error_deserialization::<Structf64Strict>(
-        r#"{"value":-1.0}"#,
-        r#"underflow when converting float to duration at line 1 column 14"#,
+    check_error_deserialization::<Struct>(
+        r#"{"value":"1.5","values":null}"#,
+        r#"invalid type: string "1.5", expected f64 at line 1 column 14"#,
     );
+}
+
+#[test]
+fn test_try_from_into() {
+    #[derive(Clone, Debug, PartialEq)]
+    struct Percent(u8);
+
+    impl TryFrom<i64> for Percent {
+        type Error = String;
+
+        fn try_from(value: i64) -> Result<Self, Self::Error> {
+            if (0..=100).contains(&value) {
+                Ok(Percent(value as u8))
+            } else {
+                Err(format!("{} is not a valid percentage", value))
+            }
+        }
+    }
+
+    impl From<Percent> for i64 {
+        fn from(percent: Percent) -> Self {
+            i64::from(percent.0)
+        }
+    }
 
     #[derive(Debug, Serialize, Deserialize, PartialEq)]
-    struct Structf64Flexible {
-        #[serde(with = "As::<DurationSeconds<f64, Flexible>>")]
-        value: Duration,
+    struct Struct {
+        #[serde(with = "As::<TryFromInto<i64>>")]
+        value: Percent,
+        #[serde(with = "As::<Vec<TryFromInto<i64>>>")]
+        values: Vec<Percent>,
     };
 
-    is_equal(Structf64Flexible { value: zero }, r#"{"value":0.0}"#);
-    is_equal(Structf64Flexible { value: one_second }, r#"{"value":1.0}"#);
-    check_serialization(Structf64Flexible { value: half_second }, r#"{"value":1.0}"#);
-    check_deserialization(
-        Structf64Flexible { value: half_second },
-        r#"{"value":"0.5"}"#,
-    );
-    check_deserialization(Structf64Flexible { value: one_second }, r#"{"value":"1"}"#);
-    check_deserialization(Structf64Flexible { value: zero }, r#"{"value":"0"}"#);
-    check_error_deserialization::<Structf64Flexible>(
-        r#"{"value":"a"}"#,
-        r#"invalid value: string "a", expected an integer, a float, or a string containing a number at line 1 column 12"#,
+    is_equal(
+        Struct {
+            value: Percent(50),
+            values: vec![Percent(0), Percent(100)],
+        },
+        r#"{"value":50,"values":[0,100]}"#,
     );
-    check_error_deserialization::<Structf64Flexible>(
-        r#"{"value":-1}"#,
-        r#"Negative values are not supported for Duration. Found -1 at line 1 column 11"#,
+    check_error_deserialization::<Struct>(
+        r#"{"value":50,"values":[0,101]}"#,
+        r#"101 is not a valid percentage at line 1 column 28"#,
     );
 
     #[derive(Debug, Serialize, Deserialize, PartialEq)]
-    struct StructStringStrict {
-        #[serde(with = "As::<DurationSeconds<String>>")]
-        value: Duration,
+    struct StructNarrow {
+        #[serde(with = "As::<TryFromInto<u8>>")]
+        value: u32,
     };
 
-    is_equal(StructStringStrict { value: zero }, r#"{"value":"0"}"#);
-    is_equal(StructStringStrict { value: one_second }, r#"{"value":"1"}"#);
-    check_serialization(
-        StructStringStrict { value: half_second },
-        r#"{"value":"1"}"#,
-    );
-    check_error_deserialization::<StructStringStrict>(
-        r#"{"value":1}"#,
-        // TODO the error message should not talk about "json object"
-        r#"invalid type: integer `1`, expected valid json object at line 1 column 10"#,
+    is_equal(StructNarrow { value: 255 }, r#"{"value":255}"#);
+    assert_eq!(
+        serde_json::to_string(&StructNarrow { value: 256 })
+            .unwrap_err()
+            .to_string(),
+        "out of range integral type conversion attempted",
     );
-    check_error_deserialization::<StructStringStrict>(
+    check_error_deserialization::<StructNarrow>(
         r#"{"value":-1}"#,
-        r#"invalid type: integer `-1`, expected valid json object at line 1 column 11"#,
+        r#"invalid value: integer `-1`, expected u8 at line 1 column 11"#,
     );
+}
 
+#[test]
+fn test_pick_first() {
     #[derive(Debug, Serialize, Deserialize, PartialEq)]
-    struct StructStringFlexible {
-        #[serde(with = "As::<DurationSeconds<String, Flexible>>")]
-        value: Duration,
+    struct Struct {
+        #[serde(with = "As::<PickFirst<(Same, DisplayFromStr)>>")]
+        value: u32,
+        #[serde(with = "As::<Vec<PickFirst<(DisplayFromStr, Same)>>>")]
+        values: Vec<u32>,
     };
 
-    is_equal(StructStringFlexible { value: zero }, r#"{"value":"0"}"#);
     is_equal(
-        StructStringFlexible { value: one_second },
-        r#"{"value":"1"}"#,
+        Struct {
+            value: 123,
+            values: vec![1, 2],
+        },
+        r#"{"value":123,"values":["1","2"]}"#,
     );
-    check_serialization(
-        StructStringFlexible { value: half_second },
-        r#"{"value":"1"}"#,
+    check_deserialization(
+        Struct {
+            value: 123,
+            values: vec![1, 2, 3],
+        },
+        r#"{"value":"123","values":[1,"2",3]}"#,
+    );
+    check_error_deserialization::<Struct>(
+        r#"{"value":"abc","values":[]}"#,
+        r#"PickFirst could not deserialize data: alternative 1 failed with: invalid type: string "abc", expected u32; alternative 2 failed with: invalid digit found in string at line 1 column 14"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructNested {
+        #[serde(
+            with = "As::<PickFirst<(Option<Same>, Option<DisplayFromStr>, DefaultOnError<Same>)>>"
+        )]
+        option: Option<u32>,
+        #[serde(with = "As::<PickFirst<(Same, BTreeMap<Same, DisplayFromStr>)>>")]
+        map: BTreeMap<String, bool>,
+    };
+
+    check_deserialization(
+        StructNested {
+            option: Some(1),
+            map: vec![("a".into(), true), ("b".into(), false)]
+                .into_iter()
+                .collect(),
+        },
+        r#"{"option":"1","map":{"a":"true","b":"false"}}"#,
+    );
+    check_deserialization(
+        StructNested {
+            option: None,
+            map: BTreeMap::new(),
+        },
+        r#"{"option":"abc","map":{}}"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructFour {
+        #[serde(with = "As::<PickFirst<(Same, DisplayFromStr, TryFromInto<i64>, FromInto<u8>)>>")]
+        value: u64,
+    };
+
+    is_equal(StructFour { value: 1 }, r#"{"value":1}"#);
+    check_error_deserialization::<StructFour>(
+        r#"{"value":-1}"#,
+        r#"PickFirst could not deserialize data: alternative 1 failed with: invalid value: integer `-1`, expected u64; alternative 2 failed with: invalid type: integer `-1`, expected valid json object; alternative 3 failed with: out of range integral type conversion attempted; alternative 4 failed with: invalid value: integer `-1`, expected u8 at line 1 column 12"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    enum Enum {
+        Unit,
+        Newtype(u32),
+        Struct { value: u32 },
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructEnum {
+        #[serde(with = "As::<Vec<PickFirst<(Same, FromInto<u32>)>>>")]
+        values: Vec<Enum>,
+    };
+
+    impl From<u32> for Enum {
+        fn from(value: u32) -> Self {
+            Enum::Newtype(value)
+        }
+    }
+
+    impl From<Enum> for u32 {
+        fn from(value: Enum) -> Self {
+            match value {
+                Enum::Unit => 0,
+                Enum::Newtype(value) | Enum::Struct { value } => value,
+            }
+        }
+    }
+
+    check_deserialization(
+        StructEnum {
+            values: vec![
+                Enum::Unit,
+                Enum::Newtype(1),
+                Enum::Struct { value: 2 },
+                Enum::Newtype(3),
+            ],
+        },
+        r#"{"values":["Unit",{"Newtype":1},{"Struct":{"value":2}},3]}"#,
+    );
+    check_error_deserialization::<StructEnum>(
+        r#"{"values":[{"Unit":null,"Newtype":1}]}"#,
+        r#"PickFirst could not deserialize data: alternative 1 failed with: invalid value: map, expected map with a single key; alternative 2 failed with: invalid type: map, expected u32 at line 1 column 37"#,
+    );
+}
+
+#[test]
+fn test_one_or_many() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Struct {
+        #[serde(with = "As::<OneOrMany>")]
+        one: Vec<String>,
+        #[serde(with = "As::<OneOrMany<DisplayFromStr, PreferMany>>")]
+        many: Vec<u32>,
+    };
+
+    is_equal(
+        Struct {
+            one: vec!["a".into()],
+            many: vec![1],
+        },
+        r#"{"one":"a","many":["1"]}"#,
+    );
+    is_equal(
+        Struct {
+            one: vec![],
+            many: vec![],
+        },
+        r#"{"one":[],"many":[]}"#,
+    );
+    is_equal(
+        Struct {
+            one: vec!["a".into(), "b".into()],
+            many: vec![1, 2],
+        },
+        r#"{"one":["a","b"],"many":["1","2"]}"#,
+    );
+    check_deserialization(
+        Struct {
+            one: vec!["a".into()],
+            many: vec![1],
+        },
+        r#"{"one":["a"],"many":"1"}"#,
+    );
+    check_error_deserialization::<Struct>(
+        r#"{"one":1,"many":[]}"#,
+        r#"OneOrMany could not deserialize data: as one element: invalid type: integer `1`, expected a string; as a sequence: invalid type: integer `1`, expected a sequence at line 1 column 8"#,
+    );
+    check_error_deserialization::<Struct>(
+        r#"{"one":[],"many":["1","x"]}"#,
+        r#"OneOrMany could not deserialize data: as one element: invalid type: sequence, expected valid json object; as a sequence: invalid digit found in string at line 1 column 27"#,
+    );
+
+    // Sequences are first tried as a single element
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructNested {
+        #[serde(with = "As::<OneOrMany<Vec<Same>>>")]
+        values: Vec<Vec<u32>>,
+    };
+
+    is_equal(
+        StructNested {
+            values: vec![vec![1, 2]],
+        },
+        r#"{"values":[1,2]}"#,
+    );
+    check_deserialization(
+        StructNested {
+            values: vec![vec![1], vec![2]],
+        },
+        r#"{"values":[[1],[2]]}"#,
+    );
+}
+
+#[test]
+fn test_key_value_map() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct User {
+        #[serde(rename = "$key$")]
+        name: String,
+        age: u8,
+        #[serde(with = "As::<Option<DisplayFromStr>>")]
+        id: Option<u32>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Struct {
+        #[serde(with = "As::<KeyValueMap<Same>>")]
+        users: Vec<User>,
+        #[serde(with = "As::<KeyValueMap<(DisplayFromStr, Same, Same)>>")]
+        tuples: Vec<(u32, bool, String)>,
+        #[serde(with = "As::<KeyValueMap<Vec<Same>>>")]
+        seqs: Vec<Vec<String>>,
+    };
+
+    is_equal(
+        Struct {
+            users: vec![
+                User {
+                    name: "alice".into(),
+                    age: 3,
+                    id: Some(1),
+                },
+                User {
+                    name: "bob".into(),
+                    age: 5,
+                    id: None,
+                },
+            ],
+            tuples: vec![(1, true, "a".into())],
+            seqs: vec![vec!["x".into(), "y".into(), "z".into()], vec!["w".into()]],
+        },
+        r#"{"users":{"alice":{"age":3,"id":"1"},"bob":{"age":5,"id":null}},"tuples":{"1":[true,"a"]},"seqs":{"x":["y","z"],"w":[]}}"#,
+    );
+    is_equal(
+        Struct {
+            users: vec![],
+            tuples: vec![],
+            seqs: vec![],
+        },
+        r#"{"users":{},"tuples":{},"seqs":{}}"#,
+    );
+    check_error_deserialization::<Struct>(
+        r#"{"users":{"alice":{"age":"3","id":null}},"tuples":{},"seqs":{}}"#,
+        r#"invalid type: string "3", expected u8 at line 1 column 28"#,
+    );
+    check_error_deserialization::<Struct>(
+        r#"{"users":{"alice":{"id":null}},"tuples":{},"seqs":{}}"#,
+        r#"missing field `age` at line 1 column 29"#,
+    );
+    check_error_deserialization::<Struct>(
+        r#"{"users":[],"tuples":{},"seqs":{}}"#,
+        r#"invalid type: sequence, expected a map at line 1 column 9"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Unnamed {
+        name: String,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructUnnamed {
+        #[serde(with = "As::<KeyValueMap<Same>>")]
+        values: Vec<Unnamed>,
+    };
+
+    assert_eq!(
+        serde_json::to_string(&StructUnnamed {
+            values: vec![Unnamed { name: "a".into() }],
+        })
+        .unwrap_err()
+        .to_string(),
+        "KeyValueMap requires the first field to be named `$key$`, but found `name`",
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructUnsupported {
+        #[serde(with = "As::<KeyValueMap<Same>>")]
+        values: Vec<u32>,
+    };
+
+    assert_eq!(
+        serde_json::to_string(&StructUnsupported { values: vec![1] })
+            .unwrap_err()
+            .to_string(),
+        "KeyValueMap only supports structs, tuples, and sequences as elements",
+    );
+}
+
+#[test]
+fn test_duration_seconds() {
+    use std::time::Duration;
+    let zero = Duration::new(0, 0);
+    let one_second = Duration::new(1, 0);
+    let half_second = Duration::new(0, 500_000_000);
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructIntStrict {
+        #[serde(with = "As::<DurationSeconds>")]
+        value: Duration,
+    };
+
+    is_equal(StructIntStrict { value: zero }, r#"{"value":0}"#);
+    is_equal(StructIntStrict { value: one_second }, r#"{"value":1}"#);
+    check_serialization(StructIntStrict { value: half_second }, r#"{"value":1}"#);
+    check_error_deserialization::<StructIntStrict>(
+        r#"{"value":"1"}"#,
+        r#"invalid type: string "1", expected u64 at line 1 column 12"#,
+    );
+    check_error_deserialization::<StructIntStrict>(
+        r#"{"value":-1}"#,
+        r#"invalid value: integer `-1`, expected u64 at line 1 column 11"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructIntFlexible {
+        #[serde(with = "As::<DurationSeconds<Integer, Flexible>>")]
+        value: Duration,
+    };
+
+    is_equal(StructIntFlexible { value: zero }, r#"{"value":0}"#);
+    is_equal(StructIntFlexible { value: one_second }, r#"{"value":1}"#);
+    check_serialization(StructIntFlexible { value: half_second }, r#"{"value":1}"#);
+    check_deserialization(
+        StructIntFlexible { value: half_second },
+        r#"{"value":"0.5"}"#,
+    );
+    check_deserialization(StructIntFlexible { value: one_second }, r#"{"value":"1"}"#);
+    check_deserialization(StructIntFlexible { value: zero }, r#"{"value":"0"}"#);
+    check_error_deserialization::<StructIntFlexible>(
+        r#"{"value":"a"}"#,
+        r#"invalid value: string "a", expected an integer, a float, or a string containing a number at line 1 column 12"#,
+    );
+    check_error_deserialization::<StructIntFlexible>(
+        r#"{"value":-1}"#,
+        r#"Negative values are not supported for Duration. Found -1 at line 1 column 11"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Structf64Strict {
+        #[serde(with = "As::<DurationSeconds<f64>>")]
+        value: Duration,
+    };
+
+    is_equal(Structf64Strict { value: zero }, r#"{"value":0.0}"#);
+    is_equal(Structf64Strict { value: one_second }, r#"{"value":1.0}"#);
+    check_serialization(Structf64Strict { value: half_second }, r#"{"value":1.0}"#);
+    check_deserialization(Structf64Strict { value: half_second }, r#"{"value":0.5}"#);
+    check_error_deserialization::<Structf64Strict>(
+        r#"{"value":"1"}"#,
+        r#"invalid type: string "1", expected f64 at line 1 column 12"#,
+    );
+    check_error_deserialization::<Structf64Strict>(
+        r#"{"value":-1.0}"#,
+        r#"underflow when converting float to duration at line 1 column 14"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Structf64Flexible {
+        #[serde(with = "As::<DurationSeconds<f64, Flexible>>")]
+        value: Duration,
+    };
+
+    is_equal(Structf64Flexible { value: zero }, r#"{"value":0.0}"#);
+    is_equal(Structf64Flexible { value: one_second }, r#"{"value":1.0}"#);
+    check_serialization(Structf64Flexible { value: half_second }, r#"{"value":1.0}"#);
+    check_deserialization(
+        Structf64Flexible { value: half_second },
+        r#"{"value":"0.5"}"#,
+    );
+    check_deserialization(Structf64Flexible { value: one_second }, r#"{"value":"1"}"#);
+    check_deserialization(Structf64Flexible { value: zero }, r#"{"value":"0"}"#);
+    check_error_deserialization::<Structf64Flexible>(
+        r#"{"value":"a"}"#,
+        r#"invalid value: string "a", expected an integer, a float, or a string containing a number at line 1 column 12"#,
+    );
+    check_error_deserialization::<Structf64Flexible>(
+        r#"{"value":-1}"#,
+        r#"Negative values are not supported for Duration. Found -1 at line 1 column 11"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructStringStrict {
+        #[serde(with = "As::<DurationSeconds<String>>")]
+        value: Duration,
+    };
+
+    is_equal(StructStringStrict { value: zero }, r#"{"value":"0"}"#);
+    is_equal(StructStringStrict { value: one_second }, r#"{"value":"1"}"#);
+    check_serialization(
+        StructStringStrict { value: half_second },
+        r#"{"value":"1"}"#,
+    );
+    check_error_deserialization::<StructStringStrict>(
+        r#"{"value":1}"#,
+        // TODO the error message should not talk about "json object"
+        r#"invalid type: integer `1`, expected valid json object at line 1 column 10"#,
+    );
+    check_error_deserialization::<StructStringStrict>(
+        r#"{"value":-1}"#,
+        r#"invalid type: integer `-1`, expected valid json object at line 1 column 11"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructStringFlexible {
+        #[serde(with = "As::<DurationSeconds<String, Flexible>>")]
+        value: Duration,
+    };
+
+    is_equal(StructStringFlexible { value: zero }, r#"{"value":"0"}"#);
+    is_equal(
+        StructStringFlexible { value: one_second },
+        r#"{"value":"1"}"#,
+    );
+    check_serialization(
+        StructStringFlexible { value: half_second },
+        r#"{"value":"1"}"#,
     );
     check_deserialization(
         StructStringFlexible { value: half_second },
@@ -685,3 +1519,851 @@ fn test_duration_seconds_with_frac() {
         r#"Negative values are not supported for Duration. Found -1 at line 1 column 11"#,
     );
 }
+
+#[test]
+fn test_duration_sub_seconds() {
+    use std::time::Duration;
+    let one_and_half_millis = Duration::new(0, 1_500_000);
+    let one_micro = Duration::new(0, 1_000);
+    let nanos = Duration::new(1, 23);
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructMillis {
+        #[serde(with = "As::<DurationMilliSeconds>")]
+        value: Duration,
+    }
+
+    is_equal(
+        StructMillis {
+            value: Duration::new(1, 0),
+        },
+        r#"{"value":1000}"#,
+    );
+    check_serialization(
+        StructMillis {
+            value: one_and_half_millis,
+        },
+        r#"{"value":2}"#,
+    );
+    check_error_deserialization::<StructMillis>(
+        r#"{"value":-1}"#,
+        r#"invalid value: integer `-1`, expected u64 at line 1 column 11"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructMillisFrac {
+        #[serde(with = "As::<DurationMilliSecondsWithFrac>")]
+        value: Duration,
+    }
+
+    is_equal(
+        StructMillisFrac {
+            value: one_and_half_millis,
+        },
+        r#"{"value":1.5}"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructMillisStringFlexible {
+        #[serde(with = "As::<DurationMilliSecondsWithFrac<String, Flexible>>")]
+        value: Duration,
+    }
+
+    is_equal(
+        StructMillisStringFlexible {
+            value: one_and_half_millis,
+        },
+        r#"{"value":"1.5"}"#,
+    );
+    check_deserialization(
+        StructMillisStringFlexible {
+            value: one_and_half_millis,
+        },
+        r#"{"value":1.5}"#,
+    );
+    check_deserialization(
+        StructMillisStringFlexible {
+            value: Duration::new(0, 123_456),
+        },
+        r#"{"value":"0.123456"}"#,
+    );
+    check_error_deserialization::<StructMillisStringFlexible>(
+        r#"{"value":"0.1234567"}"#,
+        r#"DurationMilliSecondsWithFrac only supports nanosecond precision but '0.1234567' has more than 6 fractional digits. at line 1 column 20"#,
+    );
+    check_error_deserialization::<StructMillisStringFlexible>(
+        r#"{"value":"-1.5"}"#,
+        r#"Negative values are not supported for Duration. Found -1.5 at line 1 column 15"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructMicros {
+        #[serde(with = "As::<DurationMicroSeconds<String>>")]
+        value: Duration,
+    }
+
+    is_equal(StructMicros { value: one_micro }, r#"{"value":"1"}"#);
+    is_equal(
+        StructMicros {
+            value: one_and_half_millis,
+        },
+        r#"{"value":"1500"}"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructMicrosFrac {
+        #[serde(with = "As::<DurationMicroSecondsWithFrac<String>>")]
+        value: Duration,
+    }
+
+    is_equal(
+        StructMicrosFrac { value: nanos },
+        r#"{"value":"1000000.023"}"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructNanos {
+        #[serde(with = "As::<DurationNanoSeconds>")]
+        value: Duration,
+    }
+
+    is_equal(StructNanos { value: nanos }, r#"{"value":1000000023}"#);
+    assert_eq!(
+        serde_json::to_string(&StructNanos {
+            value: Duration::new(20_000_000_000, 0),
+        })
+        .unwrap_err()
+        .to_string(),
+        "Failed to serialize value as the value cannot be represented."
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructNanosFrac {
+        #[serde(with = "As::<DurationNanoSecondsWithFrac<f64, Flexible>>")]
+        value: Duration,
+    }
+
+    is_equal(
+        StructNanosFrac { value: nanos },
+        r#"{"value":1000000023.0}"#,
+    );
+    check_deserialization(
+        StructNanosFrac { value: nanos },
+        r#"{"value":"1000000023"}"#,
+    );
+    check_error_deserialization::<StructNanosFrac>(
+        r#"{"value":"0.5"}"#,
+        r#"DurationNanoSecondsWithFrac only supports whole numbers but '0.5' has a fractional part. at line 1 column 14"#,
+    );
+
+    // `u64::MAX` nanoseconds exceed the range of `Duration` if they are not converted into seconds first
+    let max_nanos = Duration::new(18_446_744_073, 709_551_616);
+    check_deserialization(
+        StructNanosFrac { value: max_nanos },
+        r#"{"value":18446744073709551615.0}"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructNanosFracStrict {
+        #[serde(with = "As::<DurationNanoSecondsWithFrac<f64>>")]
+        value: Duration,
+    }
+
+    check_deserialization(
+        StructNanosFracStrict { value: max_nanos },
+        r#"{"value":18446744073709551615.0}"#,
+    );
+    check_error_deserialization::<StructNanosFracStrict>(
+        r#"{"value":1e30}"#,
+        r#"overflow when converting float to duration at line 1 column 14"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructMillisF64 {
+        #[serde(with = "As::<DurationMilliSeconds<f64>>")]
+        value: Duration,
+    }
+
+    check_deserialization(
+        StructMillisF64 {
+            value: one_and_half_millis,
+        },
+        r#"{"value":1.5}"#,
+    );
+}
+
+#[test]
+fn test_timestamp() {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    let one_second = UNIX_EPOCH + Duration::new(1, 0);
+    let before_epoch = UNIX_EPOCH - Duration::new(1, 0);
+    let half_before_epoch = UNIX_EPOCH - Duration::new(0, 500_000_000);
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructIntStrict {
+        #[serde(with = "As::<TimestampSeconds>")]
+        value: SystemTime,
+    }
+
+    is_equal(StructIntStrict { value: UNIX_EPOCH }, r#"{"value":0}"#);
+    is_equal(StructIntStrict { value: one_second }, r#"{"value":1}"#);
+    is_equal(
+        StructIntStrict {
+            value: before_epoch,
+        },
+        r#"{"value":-1}"#,
+    );
+    check_serialization(
+        StructIntStrict {
+            value: half_before_epoch,
+        },
+        r#"{"value":-1}"#,
+    );
+    check_error_deserialization::<StructIntStrict>(
+        r#"{"value":"1"}"#,
+        r#"invalid type: string "1", expected i64 at line 1 column 12"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructIntFlexible {
+        #[serde(with = "As::<TimestampSeconds<Integer, Flexible>>")]
+        value: SystemTime,
+    }
+
+    check_deserialization(
+        StructIntFlexible {
+            value: half_before_epoch,
+        },
+        r#"{"value":"-0.5"}"#,
+    );
+    check_deserialization(
+        StructIntFlexible {
+            value: before_epoch,
+        },
+        r#"{"value":-1.0}"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Structf64Strict {
+        #[serde(with = "As::<TimestampSecondsWithFrac>")]
+        value: SystemTime,
+    }
+
+    is_equal(
+        Structf64Strict {
+            value: half_before_epoch,
+        },
+        r#"{"value":-0.5}"#,
+    );
+    is_equal(Structf64Strict { value: one_second }, r#"{"value":1.0}"#);
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructMillisStringFlexible {
+        #[serde(with = "As::<TimestampMilliSecondsWithFrac<String, Flexible>>")]
+        value: SystemTime,
+    }
+
+    is_equal(
+        StructMillisStringFlexible {
+            value: UNIX_EPOCH - Duration::new(1, 500_500_000),
+        },
+        r#"{"value":"-1500.5"}"#,
+    );
+    check_deserialization(
+        StructMillisStringFlexible { value: one_second },
+        r#"{"value":1000}"#,
+    );
+    check_error_deserialization::<StructMillisStringFlexible>(
+        r#"{"value":"1.1234567"}"#,
+        r#"TimestampMilliSecondsWithFrac only supports nanosecond precision but '1.1234567' has more than 6 fractional digits. at line 1 column 20"#,
+    );
+    check_error_deserialization::<StructMillisStringFlexible>(
+        r#"{"value":"-+1500"}"#,
+        r#"invalid value: string "-+1500", expected an integer, a float, or a string containing a number at line 1 column 17"#,
+    );
+    check_error_deserialization::<StructMillisStringFlexible>(
+        r#"{"value":"1.+5"}"#,
+        r#"invalid value: string "1.+5", expected an integer, a float, or a string containing a number at line 1 column 15"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructNanos {
+        #[serde(with = "As::<TimestampNanoSeconds>")]
+        value: SystemTime,
+    }
+
+    is_equal(
+        StructNanos {
+            value: UNIX_EPOCH + Duration::new(1, 23),
+        },
+        r#"{"value":1000000023}"#,
+    );
+    is_equal(
+        StructNanos {
+            value: UNIX_EPOCH - Duration::new(1, 23),
+        },
+        r#"{"value":-1000000023}"#,
+    );
+
+    // `u64::MAX` nanoseconds exceed the range of `Duration` if they are not converted into seconds first
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructNanosFrac {
+        #[serde(with = "As::<TimestampNanoSecondsWithFrac<f64>>")]
+        value: SystemTime,
+    }
+
+    check_deserialization(
+        StructNanosFrac {
+            value: UNIX_EPOCH + Duration::new(18_446_744_073, 709_551_616),
+        },
+        r#"{"value":18446744073709551615.0}"#,
+    );
+    check_deserialization(
+        StructNanosFrac {
+            value: UNIX_EPOCH - Duration::new(18_446_744_073, 709_551_616),
+        },
+        r#"{"value":-18446744073709551615.0}"#,
+    );
+}
+
+#[test]
+fn test_iso8601_duration() {
+    use std::time::Duration;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructStrict {
+        #[serde(with = "As::<Iso8601Duration>")]
+        value: Duration,
+    }
+
+    is_equal(
+        StructStrict {
+            value: Duration::new(0, 0),
+        },
+        r#"{"value":"PT0S"}"#,
+    );
+    is_equal(
+        StructStrict {
+            value: Duration::new(5405, 250_000_000),
+        },
+        r#"{"value":"PT1H30M5.25S"}"#,
+    );
+    is_equal(
+        StructStrict {
+            value: Duration::new(90_000, 0),
+        },
+        r#"{"value":"PT25H"}"#,
+    );
+    is_equal(
+        StructStrict {
+            value: Duration::new(0, 1),
+        },
+        r#"{"value":"PT0.000000001S"}"#,
+    );
+    check_deserialization(
+        StructStrict {
+            value: Duration::new(5400, 0),
+        },
+        r#"{"value":"PT1.5H"}"#,
+    );
+    check_deserialization(
+        StructStrict {
+            value: Duration::new(90, 500_000_000),
+        },
+        r#"{"value":"PT0H1M30,5S"}"#,
+    );
+    check_error_deserialization::<StructStrict>(
+        r#"{"value":"P3DT4H"}"#,
+        r#"Invalid ISO 8601 duration 'P3DT4H': days are not allowed at line 1 column 18"#,
+    );
+    check_error_deserialization::<StructStrict>(
+        r#"{"value":"P1Y"}"#,
+        r#"Invalid ISO 8601 duration 'P1Y': years are not supported, as their length is ambiguous at line 1 column 15"#,
+    );
+    check_error_deserialization::<StructStrict>(
+        r#"{"value":"PT1X"}"#,
+        r#"Invalid ISO 8601 duration 'PT1X': unknown designator 'X' at line 1 column 16"#,
+    );
+    check_error_deserialization::<StructStrict>(
+        r#"{"value":"PT1S1M"}"#,
+        r#"Invalid ISO 8601 duration 'PT1S1M': minutes are repeated or in the wrong order at line 1 column 18"#,
+    );
+    check_error_deserialization::<StructStrict>(
+        r#"{"value":"PT1.5H1M"}"#,
+        r#"Invalid ISO 8601 duration 'PT1.5H1M': minutes follow a fractional component, but only the last component may have a fractional part at line 1 column 20"#,
+    );
+    check_error_deserialization::<StructStrict>(
+        r#"{"value":"PT"}"#,
+        r#"Invalid ISO 8601 duration 'PT': expected at least one component after the time designator 'T' at line 1 column 14"#,
+    );
+    check_error_deserialization::<StructStrict>(
+        r#"{"value":"1H"}"#,
+        r#"Invalid ISO 8601 duration '1H': expected the duration to start with 'P' at line 1 column 14"#,
+    );
+    check_error_deserialization::<StructStrict>(
+        r#"{"value":"-PT1S"}"#,
+        r#"Negative values are not supported for Duration. Found -1 at line 1 column 17"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructFlexible {
+        #[serde(with = "As::<Iso8601Duration<DayDesignators>>")]
+        value: Duration,
+    }
+
+    check_deserialization(
+        StructFlexible {
+            value: Duration::new(3 * 86_400 + 4 * 3600, 0),
+        },
+        r#"{"value":"P3DT4H"}"#,
+    );
+    check_deserialization(
+        StructFlexible {
+            value: Duration::new(14 * 86_400, 0),
+        },
+        r#"{"value":"P2W"}"#,
+    );
+    check_serialization(
+        StructFlexible {
+            value: Duration::new(86_400, 0),
+        },
+        r#"{"value":"PT24H"}"#,
+    );
+    check_error_deserialization::<StructFlexible>(
+        r#"{"value":"P1M"}"#,
+        r#"Invalid ISO 8601 duration 'P1M': months are not supported, as their length is ambiguous at line 1 column 15"#,
+    );
+}
+
+#[test]
+fn test_duration_human_string() {
+    use std::time::Duration;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructStrict {
+        #[serde(with = "As::<DurationHumanString>")]
+        value: Duration,
+    }
+
+    is_equal(
+        StructStrict {
+            value: Duration::new(0, 0),
+        },
+        r#"{"value":"0s"}"#,
+    );
+    is_equal(
+        StructStrict {
+            value: Duration::new(30, 0),
+        },
+        r#"{"value":"30s"}"#,
+    );
+    is_equal(
+        StructStrict {
+            value: Duration::new(0, 250_000_000),
+        },
+        r#"{"value":"250ms"}"#,
+    );
+    is_equal(
+        StructStrict {
+            value: Duration::new(1, 500_000_000),
+        },
+        r#"{"value":"1.5s"}"#,
+    );
+    is_equal(
+        StructStrict {
+            value: Duration::new(4500, 0),
+        },
+        r#"{"value":"75m"}"#,
+    );
+    is_equal(
+        StructStrict {
+            value: Duration::new(2 * 86_400, 0),
+        },
+        r#"{"value":"2d"}"#,
+    );
+    is_equal(
+        StructStrict {
+            value: Duration::new(3601, 0),
+        },
+        r#"{"value":"1h1s"}"#,
+    );
+    is_equal(
+        StructStrict {
+            value: Duration::new(0, 1_500),
+        },
+        r#"{"value":"1.5us"}"#,
+    );
+    check_deserialization(
+        StructStrict {
+            value: Duration::new(4500, 0),
+        },
+        r#"{"value":"1h15m"}"#,
+    );
+    check_deserialization(
+        StructStrict {
+            value: Duration::new(5400, 250_000_000),
+        },
+        r#"{"value":" 1h 30m 250 ms "}"#,
+    );
+    check_deserialization(
+        StructStrict {
+            value: Duration::new(5400, 0),
+        },
+        r#"{"value":"1.5h"}"#,
+    );
+    check_deserialization(
+        StructStrict {
+            value: Duration::new(43_200, 0),
+        },
+        r#"{"value":"0.5d"}"#,
+    );
+    check_deserialization(
+        StructStrict {
+            value: Duration::new(75, 0),
+        },
+        r#"{"value":"1.25m"}"#,
+    );
+    check_deserialization(
+        StructStrict {
+            value: Duration::new(0, 360_000),
+        },
+        r#"{"value":"0.0000001h"}"#,
+    );
+    check_error_deserialization::<StructStrict>(
+        r#"{"value":"30"}"#,
+        r#"Invalid duration '30': missing unit after '30' at line 1 column 13"#,
+    );
+    check_error_deserialization::<StructStrict>(
+        r#"{"value":"1x"}"#,
+        r#"Invalid duration '1x': unknown unit 'x' at line 1 column 13"#,
+    );
+    check_error_deserialization::<StructStrict>(
+        r#"{"value":"1s1h"}"#,
+        r#"Invalid duration '1s1h': hours are repeated or in the wrong order at line 1 column 15"#,
+    );
+    check_error_deserialization::<StructStrict>(
+        r#"{"value":"1.5ns"}"#,
+        r#"Invalid duration '1.5ns': the value for nanoseconds is more precise than a nanosecond at line 1 column 16"#,
+    );
+    check_error_deserialization::<StructStrict>(
+        r#"{"value":""}"#,
+        r#"Invalid duration '': expected at least one component at line 1 column 11"#,
+    );
+    check_error_deserialization::<StructStrict>(
+        r#"{"value":30}"#,
+        r#"invalid type: integer `30`, expected a duration string like "1h30m" at line 1 column 11"#,
+    );
+    check_error_deserialization::<StructStrict>(
+        r#"{"value":"-1h"}"#,
+        r#"Negative values are not supported for Duration. Found -3600 at line 1 column 15"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructFlexible {
+        #[serde(with = "As::<DurationHumanString<Flexible>>")]
+        value: Duration,
+    }
+
+    check_deserialization(
+        StructFlexible {
+            value: Duration::new(30, 0),
+        },
+        r#"{"value":30}"#,
+    );
+    check_deserialization(
+        StructFlexible {
+            value: Duration::new(1, 500_000_000),
+        },
+        r#"{"value":"1.5"}"#,
+    );
+    check_deserialization(
+        StructFlexible {
+            value: Duration::new(1, 500_000_000),
+        },
+        r#"{"value":1.5}"#,
+    );
+    check_deserialization(
+        StructFlexible {
+            value: Duration::new(90, 0),
+        },
+        r#"{"value":"1m30s"}"#,
+    );
+    check_error_deserialization::<StructFlexible>(
+        r#"{"value":"1x"}"#,
+        r#"Invalid duration '1x': unknown unit 'x' at line 1 column 13"#,
+    );
+}
+
+#[test]
+fn test_collections() {
+    use std::collections::{BTreeSet, BinaryHeap, HashSet};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructBTreeSet {
+        #[serde(with = "As::<BTreeSet<DisplayFromStr>>")]
+        values: BTreeSet<u32>,
+    }
+    is_equal(
+        StructBTreeSet {
+            values: vec![3, 1, 2].into_iter().collect(),
+        },
+        r#"{"values":["1","2","3"]}"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructHashSet {
+        #[serde(with = "As::<HashSet<DisplayFromStr>>")]
+        values: HashSet<u32>,
+    }
+    // HashSet serialization tests with more than 1 entry are unrelyable
+    is_equal(
+        StructHashSet {
+            values: vec![1].into_iter().collect(),
+        },
+        r#"{"values":["1"]}"#,
+    );
+    check_deserialization(
+        StructHashSet {
+            values: vec![1, 2, 3].into_iter().collect(),
+        },
+        r#"{"values":["3","1","2","1"]}"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructHashSetFnv {
+        #[serde(with = "As::<HashSet<DisplayFromStr, fnv::FnvBuildHasher>>")]
+        values: HashSet<u32, fnv::FnvBuildHasher>,
+    }
+    is_equal(
+        StructHashSetFnv {
+            values: vec![1].into_iter().collect(),
+        },
+        r#"{"values":["1"]}"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructLinkedList {
+        #[serde(with = "As::<LinkedList<DisplayFromStr>>")]
+        values: LinkedList<u32>,
+    }
+    is_equal(
+        StructLinkedList {
+            values: vec![3, 1, 2].into_iter().collect(),
+        },
+        r#"{"values":["3","1","2"]}"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructVecDeque {
+        #[serde(with = "As::<VecDeque<DisplayFromStr>>")]
+        values: VecDeque<u32>,
+    }
+    is_equal(
+        StructVecDeque {
+            values: vec![3, 1, 2].into_iter().collect(),
+        },
+        r#"{"values":["3","1","2"]}"#,
+    );
+    check_error_deserialization::<StructVecDeque>(
+        r#"{"values":["1","a"]}"#,
+        "invalid digit found in string at line 1 column 18",
+    );
+
+    // BinaryHeap does not implement `PartialEq`
+    #[derive(Debug, Serialize, Deserialize)]
+    struct StructBinaryHeap {
+        #[serde(with = "As::<BinaryHeap<DisplayFromStr>>")]
+        values: BinaryHeap<u32>,
+    }
+    let heap: StructBinaryHeap = serde_json::from_str(r#"{"values":["3","1","2"]}"#).unwrap();
+    assert_eq!(heap.values.into_sorted_vec(), vec![1, 2, 3]);
+    let heap = StructBinaryHeap {
+        values: vec![1].into_iter().collect(),
+    };
+    assert_eq!(serde_json::to_string(&heap).unwrap(), r#"{"values":["1"]}"#);
+}
+
+#[test]
+fn test_smart_pointers() {
+    use std::{
+        borrow::Cow,
+        cell::{Cell, RefCell},
+        sync::{Mutex, RwLock},
+    };
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructBox {
+        #[serde(with = "As::<Box<DisplayFromStr>>")]
+        value: Box<u32>,
+        #[serde(with = "As::<Box<[DisplayFromStr]>>")]
+        slice: Box<[u32]>,
+        #[serde(with = "As::<Box<Same>>")]
+        string: Box<str>,
+    }
+    is_equal(
+        StructBox {
+            value: Box::new(1),
+            slice: vec![2, 3].into_boxed_slice(),
+            string: "Hello".into(),
+        },
+        r#"{"value":"1","slice":["2","3"],"string":"Hello"}"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructRcArc {
+        #[serde(with = "As::<Rc<DisplayFromStr>>")]
+        rc: Rc<u32>,
+        #[serde(with = "As::<Arc<Vec<DisplayFromStr>>>")]
+        arc: Arc<Vec<u32>>,
+    }
+    is_equal(
+        StructRcArc {
+            rc: Rc::new(1),
+            arc: Arc::new(vec![2, 3]),
+        },
+        r#"{"rc":"1","arc":["2","3"]}"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructCow {
+        #[serde(with = "As::<Cow<'static, [DisplayFromStr]>>")]
+        value: Cow<'static, [u32]>,
+        #[serde(with = "As::<Cow<'static, Same>>")]
+        string: Cow<'static, str>,
+    }
+    is_equal(
+        StructCow {
+            value: Cow::Borrowed(&[1, 2]),
+            string: Cow::Borrowed("Hello"),
+        },
+        r#"{"value":["1","2"],"string":"Hello"}"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructCell {
+        #[serde(with = "As::<Cell<DisplayFromStr>>")]
+        cell: Cell<u32>,
+        #[serde(with = "As::<RefCell<DisplayFromStr>>")]
+        ref_cell: RefCell<u32>,
+    }
+    is_equal(
+        StructCell {
+            cell: Cell::new(1),
+            ref_cell: RefCell::new(2),
+        },
+        r#"{"cell":"1","ref_cell":"2"}"#,
+    );
+
+    let value = StructCell {
+        cell: Cell::new(1),
+        ref_cell: RefCell::new(2),
+    };
+    let _borrow = value.ref_cell.borrow_mut();
+    assert_eq!(
+        serde_json::to_string(&value).unwrap_err().to_string(),
+        "already mutably borrowed"
+    );
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct StructLock {
+        #[serde(with = "As::<Mutex<DisplayFromStr>>")]
+        mutex: Mutex<u32>,
+        #[serde(with = "As::<RwLock<DisplayFromStr>>")]
+        rw_lock: RwLock<u32>,
+    }
+    let value = StructLock {
+        mutex: Mutex::new(1),
+        rw_lock: RwLock::new(2),
+    };
+    assert_eq!(
+        serde_json::to_string(&value).unwrap(),
+        r#"{"mutex":"1","rw_lock":"2"}"#
+    );
+    let value: StructLock = serde_json::from_str(r#"{"mutex":"3","rw_lock":"4"}"#).unwrap();
+    assert_eq!(*value.mutex.lock().unwrap(), 3);
+    assert_eq!(*value.rw_lock.read().unwrap(), 4);
+
+    // Poison the mutex
+    let value = Arc::new(value);
+    let value2 = Arc::clone(&value);
+    let _ = std::thread::spawn(move || {
+        let _guard = value2.mutex.lock().unwrap();
+        panic!("poison the mutex");
+    })
+    .join();
+    assert_eq!(
+        serde_json::to_string(&*value).unwrap_err().to_string(),
+        "lock poison error while serializing"
+    );
+}
+
+#[test]
+fn test_deserialize_in_place() {
+    use std::collections::BTreeSet;
+
+    fn deserialize_in_place<T, I>(s: &str, place: &mut I)
+    where
+        T: for<'de> serde_with::de::DeserializeAs<'de, I>,
+    {
+        let mut deserializer = serde_json::Deserializer::from_str(s);
+        As::<T>::deserialize_in_place(&mut deserializer, place).unwrap();
+        deserializer.end().unwrap();
+    }
+
+    // The allocation of the `Vec` is reused
+    let mut values: Vec<u32> = Vec::with_capacity(100);
+    values.extend(&[1, 2, 3]);
+    let ptr = values.as_ptr();
+    deserialize_in_place::<Vec<DisplayFromStr>, _>(r#"["4","5"]"#, &mut values);
+    assert_eq!(values, vec![4, 5]);
+    assert_eq!(values.as_ptr(), ptr);
+    deserialize_in_place::<Vec<DisplayFromStr>, _>(r#"["6","7","8","9"]"#, &mut values);
+    assert_eq!(values, vec![6, 7, 8, 9]);
+    assert_eq!(values.as_ptr(), ptr);
+
+    // Nested buffers are reused too
+    let mut values: Vec<Vec<u32>> = vec![Vec::with_capacity(100)];
+    let ptr = values[0].as_ptr();
+    deserialize_in_place::<Vec<Vec<DisplayFromStr>>, _>(r#"[["1","2"],["3"]]"#, &mut values);
+    assert_eq!(values, vec![vec![1, 2], vec![3]]);
+    assert_eq!(values[0].as_ptr(), ptr);
+
+    let mut values: Option<Vec<u32>> = Some(Vec::with_capacity(100));
+    let ptr = values.as_ref().unwrap().as_ptr();
+    deserialize_in_place::<Option<Vec<DisplayFromStr>>, _>(r#"["1"]"#, &mut values);
+    assert_eq!(values, Some(vec![1]));
+    assert_eq!(values.as_ref().unwrap().as_ptr(), ptr);
+    deserialize_in_place::<Option<Vec<DisplayFromStr>>, _>(r#"null"#, &mut values);
+    assert_eq!(values, None);
+    deserialize_in_place::<Option<Vec<DisplayFromStr>>, _>(r#"["2"]"#, &mut values);
+    assert_eq!(values, Some(vec![2]));
+
+    let mut values: HashMap<u32, u32> = HashMap::with_capacity(100);
+    values.insert(1, 1);
+    let capacity = values.capacity();
+    deserialize_in_place::<HashMap<DisplayFromStr, Same>, _>(r#"{"2":2}"#, &mut values);
+    assert_eq!(values, vec![(2, 2)].into_iter().collect());
+    assert_eq!(values.capacity(), capacity);
+
+    let mut values: BTreeSet<u32> = vec![1, 2].into_iter().collect();
+    deserialize_in_place::<BTreeSet<DisplayFromStr>, _>(r#"["3"]"#, &mut values);
+    assert_eq!(values, vec![3].into_iter().collect());
+
+    let mut values: VecDeque<u32> = VecDeque::with_capacity(100);
+    values.push_back(1);
+    let capacity = values.capacity();
+    deserialize_in_place::<VecDeque<DisplayFromStr>, _>(r#"["2","3"]"#, &mut values);
+    assert_eq!(values, VecDeque::from(vec![2, 3]));
+    assert_eq!(values.capacity(), capacity);
+
+    let mut values: Box<Vec<u32>> = Box::new(Vec::with_capacity(100));
+    let ptr = values.as_ptr();
+    deserialize_in_place::<Box<Vec<DisplayFromStr>>, _>(r#"["1"]"#, &mut values);
+    assert_eq!(*values, vec![1]);
+    assert_eq!(values.as_ptr(), ptr);
+
+    // Types without a specialized implementation are overwritten
+    let mut value = 1u32;
+    deserialize_in_place::<DisplayFromStr, _>(r#""2""#, &mut value);
+    assert_eq!(value, 2);
+}
@@ -0,0 +1,133 @@
+#![cfg(feature = "macros")]
+
+mod utils;
+
+use crate::utils::{
+    check_deserialization, check_error_deserialization, check_serialization, is_equal,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr, Same};
+use std::collections::BTreeMap;
+
+#[test]
+fn test_serde_as_basic() {
+    #[serde_as]
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Struct {
+        #[serde_as(as = "DisplayFromStr")]
+        a: u32,
+        #[serde_as(as = "Vec<DisplayFromStr>")]
+        b: Vec<u32>,
+        #[serde_as(as = "BTreeMap<DisplayFromStr, Same>")]
+        c: BTreeMap<u32, u32>,
+        #[serde_as(as = "Vec<(_, DisplayFromStr)>")]
+        d: Vec<(u32, u32)>,
+        e: u32,
+    }
+
+    is_equal(
+        Struct {
+            a: 1,
+            b: vec![2, 3],
+            c: vec![(4, 5)].into_iter().collect(),
+            d: vec![(6, 7)],
+            e: 8,
+        },
+        r#"{"a":"1","b":["2","3"],"c":{"4":5},"d":[[6,"7"]],"e":8}"#,
+    );
+}
+
+#[test]
+fn test_serde_as_option_default() {
+    #[serde_as]
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Struct {
+        #[serde_as(as = "Option<DisplayFromStr>")]
+        a: Option<u32>,
+        #[serde_as(deserialize_as = "Option<DisplayFromStr>")]
+        b: Option<u32>,
+        #[serde_as(as = "Option<DisplayFromStr>")]
+        #[serde(default = "default_c")]
+        c: Option<u32>,
+    }
+
+    fn default_c() -> Option<u32> {
+        Some(42)
+    }
+
+    check_serialization(
+        Struct {
+            a: Some(1),
+            b: Some(2),
+            c: None,
+        },
+        r#"{"a":"1","b":2,"c":null}"#,
+    );
+    check_deserialization(
+        Struct {
+            a: Some(1),
+            b: Some(2),
+            c: None,
+        },
+        r#"{"a":"1","b":"2","c":null}"#,
+    );
+    check_deserialization(
+        Struct {
+            a: None,
+            b: None,
+            c: Some(42),
+        },
+        r#"{}"#,
+    );
+}
+
+#[test]
+fn test_serde_as_serialize_deserialize_as() {
+    #[serde_as]
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Struct {
+        #[serde_as(serialize_as = "DisplayFromStr", deserialize_as = "Same")]
+        a: u32,
+        #[serde_as(serialize_as = "Vec<DisplayFromStr>")]
+        b: Vec<u32>,
+    }
+
+    let value = Struct {
+        a: 1,
+        b: vec![2, 3],
+    };
+    assert_eq!(
+        serde_json::to_string(&value).unwrap(),
+        r#"{"a":"1","b":["2","3"]}"#
+    );
+    check_deserialization(value, r#"{"a":1,"b":[2,3]}"#);
+    check_error_deserialization::<Struct>(
+        r#"{"a":"1","b":[2,3]}"#,
+        r#"invalid type: string "1", expected u32 at line 1 column 8"#,
+    );
+}
+
+#[test]
+fn test_serde_as_enum_and_tuple_struct() {
+    #[serde_as]
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Tuple(#[serde_as(as = "DisplayFromStr")] u32, u32);
+
+    is_equal(Tuple(1, 2), r#"["1",2]"#);
+
+    #[serde_as]
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    enum Enum {
+        Tuple(#[serde_as(as = "DisplayFromStr")] u32),
+        Struct {
+            #[serde_as(as = "Vec<DisplayFromStr>")]
+            values: Vec<u32>,
+        },
+    }
+
+    is_equal(Enum::Tuple(1), r#"{"Tuple":"1"}"#);
+    is_equal(
+        Enum::Struct { values: vec![1, 2] },
+        r#"{"Struct":{"values":["1","2"]}}"#,
+    );
+}
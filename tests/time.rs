@@ -0,0 +1,256 @@
+#![cfg(feature = "time")]
+
+mod utils;
+
+use crate::utils::{
+    check_deserialization, check_error_deserialization, check_serialization, is_equal,
+};
+use serde::Serialize;
+use serde_derive::Deserialize;
+use serde_with::{
+    As, DayDesignators, DurationHumanString, DurationMilliSeconds, DurationSeconds,
+    DurationSecondsWithFrac, Flexible, Iso8601Duration, TimestampMilliSeconds,
+    TimestampNanoSecondsWithFrac, TimestampSeconds, TimestampSecondsWithFrac,
+};
+use time_crate::{Duration, OffsetDateTime, PrimitiveDateTime};
+
+fn new_datetime(secs: i64, nanos: i64) -> OffsetDateTime {
+    OffsetDateTime::unix_epoch() + Duration::seconds(secs) + Duration::nanoseconds(nanos)
+}
+
+fn new_naive(secs: i64, nanos: i64) -> PrimitiveDateTime {
+    let epoch = OffsetDateTime::unix_epoch();
+    PrimitiveDateTime::new(epoch.date(), epoch.time())
+        + Duration::seconds(secs)
+        + Duration::nanoseconds(nanos)
+}
+
+#[test]
+fn test_time_timestamp_seconds() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructIntStrict {
+        #[serde(with = "As::<TimestampSeconds>")]
+        offset: OffsetDateTime,
+        #[serde(with = "As::<TimestampSeconds>")]
+        primitive: PrimitiveDateTime,
+    }
+
+    is_equal(
+        StructIntStrict {
+            offset: new_datetime(1_478_563_200, 0),
+            primitive: new_naive(1_478_563_200, 0),
+        },
+        r#"{"offset":1478563200,"primitive":1478563200}"#,
+    );
+    is_equal(
+        StructIntStrict {
+            offset: new_datetime(-86_000, 0),
+            primitive: new_naive(-86_000, 0),
+        },
+        r#"{"offset":-86000,"primitive":-86000}"#,
+    );
+    check_error_deserialization::<StructIntStrict>(
+        r#"{"offset":"1478563200","primitive":0}"#,
+        r#"invalid type: string "1478563200", expected i64 at line 1 column 22"#,
+    );
+    check_error_deserialization::<StructIntStrict>(
+        r#"{"offset":0,"primitive":9223372036854775807}"#,
+        r#"Invalid or out of range value '9223372036854775807' for a date and time at line 1 column 44"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructFlexible {
+        #[serde(with = "As::<TimestampSecondsWithFrac<String, Flexible>>")]
+        value: OffsetDateTime,
+    }
+
+    is_equal(
+        StructFlexible {
+            value: new_datetime(1_478_563_200, 123_000_000),
+        },
+        r#"{"value":"1478563200.123"}"#,
+    );
+    check_deserialization(
+        StructFlexible {
+            value: new_datetime(1_478_563_200, 0),
+        },
+        r#"{"value":1478563200}"#,
+    );
+    check_deserialization(
+        StructFlexible {
+            value: new_datetime(-86_001, 1_000_000),
+        },
+        r#"{"value":"-86000.999"}"#,
+    );
+    check_deserialization(
+        StructFlexible {
+            value: new_datetime(-2, 500_000_000),
+        },
+        r#"{"value":-1.5}"#,
+    );
+    check_error_deserialization::<StructFlexible>(
+        r#"{"value":"a"}"#,
+        r#"invalid value: string "a", expected an integer, a float, or a string containing a number at line 1 column 12"#,
+    );
+}
+
+#[test]
+fn test_time_timestamp_sub_seconds() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructMilli {
+        #[serde(with = "As::<TimestampMilliSeconds>")]
+        value: PrimitiveDateTime,
+    }
+
+    is_equal(
+        StructMilli {
+            value: new_naive(1, 500_000_000),
+        },
+        r#"{"value":1500}"#,
+    );
+    check_serialization(
+        StructMilli {
+            value: new_naive(0, 1_500_000),
+        },
+        r#"{"value":2}"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructNano {
+        #[serde(with = "As::<TimestampNanoSecondsWithFrac<String>>")]
+        value: OffsetDateTime,
+    }
+
+    is_equal(
+        StructNano {
+            value: new_datetime(-1, 0),
+        },
+        r#"{"value":"-1000000000"}"#,
+    );
+}
+
+#[test]
+fn test_time_duration_seconds() {
+    let one_second = Duration::seconds(1);
+    let minus_one_and_half = -Duration::milliseconds(1_500);
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructIntStrict {
+        #[serde(with = "As::<DurationSeconds>")]
+        value: Duration,
+    }
+
+    is_equal(StructIntStrict { value: one_second }, r#"{"value":1}"#);
+    is_equal(StructIntStrict { value: -one_second }, r#"{"value":-1}"#);
+    check_serialization(
+        StructIntStrict {
+            value: minus_one_and_half,
+        },
+        r#"{"value":-2}"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructStringStrict {
+        #[serde(with = "As::<DurationSecondsWithFrac<String>>")]
+        value: Duration,
+    }
+
+    is_equal(
+        StructStringStrict {
+            value: minus_one_and_half,
+        },
+        r#"{"value":"-1.5"}"#,
+    );
+    is_equal(
+        StructStringStrict {
+            value: -Duration::nanoseconds(1),
+        },
+        r#"{"value":"-0.000000001"}"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Structf64Flexible {
+        #[serde(with = "As::<DurationSecondsWithFrac<f64, Flexible>>")]
+        value: Duration,
+    }
+
+    is_equal(
+        Structf64Flexible {
+            value: minus_one_and_half,
+        },
+        r#"{"value":-1.5}"#,
+    );
+    check_deserialization(
+        Structf64Flexible {
+            value: minus_one_and_half,
+        },
+        r#"{"value":"-1.5"}"#,
+    );
+    check_error_deserialization::<Structf64Flexible>(
+        r#"{"value":18446744073709551615}"#,
+        r#"Invalid or out of range value '18446744073709551615' for time::Duration at line 1 column 30"#,
+    );
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct StructMilli {
+        #[serde(with = "As::<DurationMilliSeconds<String, Flexible>>")]
+        value: Duration,
+    }
+
+    is_equal(
+        StructMilli {
+            value: -Duration::milliseconds(1_500),
+        },
+        r#"{"value":"-1500"}"#,
+    );
+    check_deserialization(
+        StructMilli {
+            value: Duration::microseconds(2_500),
+        },
+        r#"{"value":2.5}"#,
+    );
+}
+
+#[test]
+fn test_time_iso8601_duration() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Struct {
+        #[serde(with = "As::<Iso8601Duration<DayDesignators>>")]
+        value: Duration,
+    }
+
+    is_equal(
+        Struct {
+            value: Duration::milliseconds(5_405_250),
+        },
+        r#"{"value":"PT1H30M5.25S"}"#,
+    );
+    check_deserialization(
+        Struct {
+            value: -Duration::hours(76),
+        },
+        r#"{"value":"-P3DT4H"}"#,
+    );
+}
+
+#[test]
+fn test_time_duration_human_string() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Struct {
+        #[serde(with = "As::<DurationHumanString>")]
+        value: Duration,
+    }
+
+    is_equal(
+        Struct {
+            value: Duration::minutes(75),
+        },
+        r#"{"value":"75m"}"#,
+    );
+    is_equal(
+        Struct {
+            value: -Duration::milliseconds(250),
+        },
+        r#"{"value":"-250ms"}"#,
+    );
+}